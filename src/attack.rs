@@ -1,2 +1,12 @@
+pub mod cbc_mac;
+pub mod compression;
+pub mod dh;
+pub mod dsa;
+pub mod factor;
+pub mod kangaroo;
+pub mod md4;
+pub mod merkle_damgard;
+pub mod rc4;
+pub mod rsa;
 pub mod score;
 pub mod xor;
@@ -0,0 +1,3 @@
+pub mod collision;
+pub mod iv_forgery;
+pub mod length_extension;
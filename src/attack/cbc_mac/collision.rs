@@ -0,0 +1,78 @@
+use crate::{Data, Error, Result, cipher::CbcMac, error::InvalidLengthType};
+
+/// How many spaces to try appending to the prefix before giving up.
+const MAX_SPACES: usize = 256;
+
+/// Forges a snippet that starts with `prefix` but has the same CBC-MAC hash as `target`. The glue
+/// block xors the MAC of the padded prefix into the first block of `target`, after which the chain
+/// runs through `target` unchanged. `prefix` should end in a `//` comment to hide the padding and
+/// glue, so spaces are appended to it until neither contains a line terminator.
+pub fn forge_snippet(mac: &CbcMac, prefix: &Data, target: &Data) -> Result<Data> {
+    if target.len() < 16 {
+        return Err(Error::InvalidLength {
+            kind: InvalidLengthType::Message,
+            expected: 16,
+            actual: target.len(),
+        });
+    }
+
+    for spaces in 0..MAX_SPACES {
+        let bytes: Box<[u8]> = prefix
+            .iter()
+            .copied()
+            .chain(std::iter::repeat_n(b' ', spaces))
+            .collect();
+        let prefix = Data::from(bytes);
+
+        let state = mac.mac(&prefix)?;
+        let glue: Box<[u8]> = prefix
+            .pad(16)
+            .iter()
+            .skip(prefix.len())
+            .copied()
+            .chain(state.iter().zip(target.iter()).map(|(a, b)| a ^ b))
+            .collect();
+        if glue.iter().any(|b| matches!(b, b'\n' | b'\r')) {
+            continue;
+        }
+
+        let bytes: Box<[u8]> = prefix
+            .iter()
+            .chain(&glue)
+            .chain(&target[16..])
+            .copied()
+            .collect();
+        return Ok(Data::from(bytes));
+    }
+
+    Err(Error::AttackFailed(
+        "every glue block contained a line break",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn s7c50_hashing_with_cbc_mac() -> Result<()> {
+        let mac = CbcMac::new("YELLOW SUBMARINE")?;
+        let target = Data::from("alert('MZA who was that?');\n".as_bytes());
+        let prefix = Data::from("alert('Ayo, the Wu is back!');//".as_bytes());
+
+        let forged = forge_snippet(&mac, &prefix, &target)?;
+        assert!(forged.starts_with(&prefix));
+        assert!(forged.ends_with(&target[16..]));
+        assert_eq!(mac.mac(&target)?, mac.mac(&forged)?);
+        assert_eq!("296b8d7cb78a243dda4d0a61d33bbdd1", mac.mac(&forged)?.hex());
+
+        // everything between the comment and the tail stays on one line
+        let line = &forged[prefix.len()..forged.len() - target.len() + 16];
+        assert!(!line.contains(&b'\n'));
+
+        Ok(())
+    }
+}
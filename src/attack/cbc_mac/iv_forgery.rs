@@ -0,0 +1,67 @@
+use crate::{Data, Error, Result, blackbox::cbc_mac_bank::Transfer, error::InvalidLengthType};
+
+/// Rewrites a `message || IV || MAC` transfer request to spend from `victim` instead. Changing the
+/// first block of the message only changes what gets xored into the first block cipher call, so
+/// the same change applied to the IV leaves the MAC intact. The new sender id has to leave
+/// everything past the first block untouched.
+pub fn forge_transfer(request: &Data, victim: u32) -> Result<Data> {
+    let len = request.len();
+    if len < 32 {
+        return Err(Error::InvalidLength {
+            kind: InvalidLengthType::Message,
+            expected: 32,
+            actual: len,
+        });
+    }
+
+    let (message, rest) = request.split_at(len - 32);
+    let (iv, mac) = rest.split_at(16);
+
+    let mut transfer = Transfer::parse(message)?;
+    transfer.from = victim;
+    let forged = transfer.encode();
+
+    if forged.len() != message.len() || forged.get(16..) != message.get(16..) {
+        return Err(Error::AttackFailed(
+            "the new sender changes more than the first block",
+        ));
+    }
+
+    let mut iv = iv.to_vec();
+    for ((b, old), new) in iv.iter_mut().zip(message).zip(forged.iter()) {
+        *b ^= old ^ new;
+    }
+
+    let bytes: Box<[u8]> = forged.iter().chain(&iv).chain(mac).copied().collect();
+    Ok(Data::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::blackbox::cbc_mac_bank::Bank;
+
+    #[test]
+    fn s7c49_cbc_mac_message_forgery_controlled_iv() -> Result<()> {
+        let bank = Bank::new();
+        let attacker = bank.client(31);
+
+        let request = attacker.transfer(31, 1_000_000)?;
+        let forged = forge_transfer(&request, 42)?;
+        assert_eq!(
+            Transfer {
+                from: 42,
+                to: 31,
+                amount: 1_000_000
+            },
+            bank.transfer(&forged)?
+        );
+
+        assert!(forge_transfer(&request, 420).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{Data, Error, Result, blackbox::cbc_mac_bank::Batch, error::InvalidLengthType};
+
+fn split(request: &Data) -> Result<(&[u8], &[u8])> {
+    let len = request.len();
+    if len < 16 {
+        return Err(Error::InvalidLength {
+            kind: InvalidLengthType::Message,
+            expected: 16,
+            actual: len,
+        });
+    }
+
+    Ok(request.split_at(len - 16))
+}
+
+/// Splices the attacker's own `message || MAC` batch onto a captured one. With a fixed IV, the
+/// captured MAC is the chaining value after the victim's padded message, so xoring it into the
+/// attacker's first block puts the chain back where the attacker's message started, and the
+/// attacker's MAC carries over. The first block of the attacker's batch turns to garbage, so its
+/// last transaction must start after it; fails if the garbage corrupts the spliced batch, in which
+/// case another capture will do.
+pub fn extend_batch(captured: &Data, own: &Data) -> Result<Data> {
+    let (victim, victim_mac) = split(captured)?;
+    let (message, mac) = split(own)?;
+    if message.len() < 16 {
+        return Err(Error::InvalidLength {
+            kind: InvalidLengthType::Message,
+            expected: 16,
+            actual: message.len(),
+        });
+    }
+
+    let padded = Data::from(victim).pad(16);
+    let glue = message[..16].iter().zip(victim_mac).map(|(a, b)| a ^ b);
+    let forged: Box<[u8]> = padded
+        .iter()
+        .copied()
+        .chain(glue)
+        .chain(message[16..].iter().copied())
+        .collect();
+
+    let expected = Batch::parse(message)?.transactions.last().copied();
+    let from = Batch::parse(victim)?.from;
+    let spliced =
+        Batch::parse(&forged).map_err(|_| Error::AttackFailed("garbage broke the batch"))?;
+    if spliced.from != from || spliced.transactions.last().copied() != expected {
+        return Err(Error::AttackFailed("garbage swallowed the transaction"));
+    }
+
+    let bytes: Box<[u8]> = forged.iter().chain(mac).copied().collect();
+    Ok(Data::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use super::*;
+    use crate::blackbox::cbc_mac_bank::{Bank, Transaction};
+
+    #[test]
+    fn s7c49_cbc_mac_message_forgery_length_extension() -> Result<()> {
+        let bank = Bank::new();
+        let victim = bank.client(42);
+        let attacker = bank.client(31);
+
+        // the first transaction is sacrificed to the garbage block
+        let own = attacker.batch(vec![
+            Transaction::new(31, 0),
+            Transaction::new(31, 1_000_000),
+        ])?;
+
+        let forged = (1..)
+            .map(|amount| victim.batch(vec![Transaction::new(7, amount), Transaction::new(8, 5)]))
+            .find_map(|captured| extend_batch(&captured.ok()?, &own).ok())
+            .unwrap();
+
+        let batch = bank.batch(&forged)?;
+        assert_eq!(42, batch.from);
+        assert!(
+            batch
+                .transactions
+                .contains(&Transaction::new(31, 1_000_000))
+        );
+
+        Ok(())
+    }
+}
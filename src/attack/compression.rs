@@ -0,0 +1,79 @@
+use crate::{Data, Error, Result, blackbox::Oracle};
+
+/// The base64 alphabet, plus the newline that ends the header.
+const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=\n";
+const MAX_PADDING: u8 = 0x80;
+const MAX_COOKIE_LEN: usize = 256;
+
+/// Recovers the value of the cookie `name` from an oracle that leaks compressed lengths, one
+/// character at a time: a guess that extends the match against the real cookie compresses better.
+pub fn recover_cookie<O: Oracle<Output = usize>>(oracle: &mut O, name: &str) -> Result<String> {
+    let mut known = format!("{name}=").into_bytes();
+    let start = known.len();
+
+    while known.len() - start < MAX_COOKIE_LEN {
+        match next_byte(oracle, &known)? {
+            b'\n' => return Ok(String::from_utf8_lossy(&known[start..]).into_owned()),
+            b => known.push(b),
+        }
+    }
+
+    Err(Error::AttackFailed("cookie never ended"))
+}
+
+/// Tries each candidate behind growing amounts of incompressible padding, keeping only the ones
+/// that compress best. The padding shifts where the output crosses a byte or block boundary, so
+/// eventually a one-bit difference between guesses shows up in the length, even through CBC.
+fn next_byte<O: Oracle<Output = usize>>(oracle: &mut O, known: &[u8]) -> Result<u8> {
+    let mut candidates = ALPHABET.to_vec();
+
+    // bytes above 0x7f never appear in the request, so nothing can match against them
+    for len in 0..MAX_PADDING {
+        let lengths = candidates
+            .iter()
+            .map(|&c| {
+                let body: Box<[u8]> = (0x80..0x80 + len)
+                    .chain(known.iter().copied())
+                    .chain([c])
+                    .collect();
+                oracle.query(&Data::from(body))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let min = lengths.iter().copied().min().unwrap_or_default();
+        candidates = candidates
+            .into_iter()
+            .zip(lengths)
+            .filter_map(|(c, l)| (l == min).then_some(c))
+            .collect();
+
+        if let [c] = candidates[..] {
+            return Ok(c);
+        }
+    }
+
+    Err(Error::AttackFailed("no padding separated the candidates"))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::blackbox::compression::{COOKIE, CompressionOracle, CtrOrCbc};
+
+    #[test]
+    fn s7c51_compression_ratio_side_channel_attacks_ctr() -> Result<()> {
+        let mut oracle = CompressionOracle::new(CtrOrCbc::Ctr);
+        assert_eq!(COOKIE, recover_cookie(&mut oracle, "sessionid")?);
+        Ok(())
+    }
+
+    #[test]
+    fn s7c51_compression_ratio_side_channel_attacks_cbc() -> Result<()> {
+        let mut oracle = CompressionOracle::new(CtrOrCbc::Cbc);
+        assert_eq!(COOKIE, recover_cookie(&mut oracle, "sessionid")?);
+        Ok(())
+    }
+}
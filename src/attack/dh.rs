@@ -0,0 +1,234 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::One;
+use rand::Rng;
+
+use crate::{
+    Data, Error, Result,
+    attack::{
+        factor::primes,
+        kangaroo::{Jumps, kangaroo},
+    },
+    blackbox::{Oracle, dh_mac::mac},
+    dh::{Parameters, PublicKey},
+    group::Group,
+    math::{crt, random_below},
+};
+
+/// Finds the distinct primes up to `bound` that divide the cofactor, each of which is the order of
+/// some subgroup that `q` knows nothing about.
+#[must_use]
+pub fn small_factors(params: &Parameters, bound: u64) -> Vec<u64> {
+    let cofactor = params.cofactor();
+    primes(bound)
+        .filter(|&r| cofactor.is_multiple_of(&BigUint::from(r)))
+        .collect()
+}
+
+/// Finds an element of order `r` by raising random elements to `(p - 1) / r` until one of them
+/// lands somewhere other than `1`.
+fn element_of_order<R: Rng + ?Sized>(params: &Parameters, r: u64, rng: &mut R) -> BigUint {
+    let exponent = (&params.p - 1u32) / r;
+    loop {
+        let h = random_below(&params.p, rng).modpow(&exponent, &params.p);
+        if h > BigUint::one() {
+            return h;
+        }
+    }
+}
+
+/// Confines the oracle's shared secret to the subgroup of order `r` and brute-forces its key
+/// modulo `r` from the MAC it answers with.
+fn residue<O, R>(oracle: &mut O, params: &Parameters, r: u64, rng: &mut R) -> Result<BigUint>
+where
+    O: Oracle<Output = (Data, Data)>,
+    R: Rng + ?Sized,
+{
+    let h = element_of_order(params, r, rng);
+    let (message, tag) = oracle.query(&Data::from_biguint(&h))?;
+
+    let mut secret = BigUint::one();
+    for b in 0..r {
+        if mac(&secret, &message) == tag {
+            return Ok(BigUint::from(b));
+        }
+        secret = secret * &h % &params.p;
+    }
+
+    Err(Error::AttackFailed("no residue reproduces the MAC"))
+}
+
+/// Collects the oracle's private key modulo each small factor of the cofactor up to
+/// `factor_bound`, stopping early once the moduli multiply past `q`. Each factor costs one query
+/// and up to `r` MACs.
+pub fn residues<O, R>(
+    oracle: &mut O,
+    params: &Parameters,
+    factor_bound: u64,
+    rng: &mut R,
+) -> Result<Vec<(BigUint, BigUint)>>
+where
+    O: Oracle<Output = (Data, Data)>,
+    R: Rng + ?Sized,
+{
+    let mut res = vec![];
+    let mut modulus = BigUint::one();
+
+    for r in small_factors(params, factor_bound) {
+        if modulus > params.q {
+            break;
+        }
+
+        let b = residue(oracle, params, r, rng)?;
+        modulus *= r;
+        res.push((b, BigUint::from(r)));
+    }
+
+    Ok(res)
+}
+
+/// Pohlig-Hellman on the oracle's subgroup confinement: combines the residues with CRT, which
+/// pins down the whole key once the small factors up to `factor_bound` cover `q`.
+pub fn recover_key<O, R>(
+    oracle: &mut O,
+    params: &Parameters,
+    factor_bound: u64,
+    rng: &mut R,
+) -> Result<BigUint>
+where
+    O: Oracle<Output = (Data, Data)>,
+    R: Rng + ?Sized,
+{
+    let residues = residues(oracle, params, factor_bound, rng)?;
+    let (x, modulus) = crt(&residues)?;
+    if modulus <= params.q {
+        return Err(Error::AttackFailed(
+            "the small factors don't cover the subgroup order",
+        ));
+    }
+
+    Ok(x)
+}
+
+/// Finishes off Pohlig-Hellman with the kangaroo: given `x = n mod r` for the log `x` of `y`,
+/// `x = n + m * r` where `y * g^-n = (g^r)^m` and `m` is at most `q / r`, which the kangaroo
+/// searches in around `sqrt(q / r)` steps.
+pub fn kangaroo_from_residue<G: Group>(
+    group: &G,
+    y: &G::Element,
+    n: &BigUint,
+    r: &BigUint,
+) -> Result<BigUint> {
+    let g = group.generator();
+    let Some(inv) = group.invert(&group.scale(&g, n)) else {
+        return Err(Error::AttackFailed("g^n is not invertible"));
+    };
+    let y = group.combine(y, &inv);
+    let bound = (group.order() - 1u32) / r;
+    let Ok(width) = u64::try_from(&bound) else {
+        return Err(Error::AttackFailed(
+            "the small factors leave too much for the kangaroo",
+        ));
+    };
+
+    let m = kangaroo(
+        group,
+        &group.scale(&g, r),
+        &y,
+        &BigUint::ZERO,
+        &bound,
+        Jumps::for_width(width),
+    )
+    .ok_or(Error::AttackFailed("the wild kangaroo overshot"))?;
+    Ok(n + m * r)
+}
+
+/// Pohlig-Hellman for when the small factors fall short of `q`: collects what residues there are
+/// and leaves the rest to [`kangaroo_from_residue`].
+pub fn recover_key_with_kangaroo<O, R>(
+    oracle: &mut O,
+    public: &PublicKey,
+    factor_bound: u64,
+    rng: &mut R,
+) -> Result<BigUint>
+where
+    O: Oracle<Output = (Data, Data)>,
+    R: Rng + ?Sized,
+{
+    let params = &public.params;
+    let residues = residues(oracle, params, factor_bound, rng)?;
+    let (n, r) = crt(&residues)?;
+    if r > params.q {
+        return Ok(n);
+    }
+
+    kangaroo_from_residue(params, &public.y, &n, &r)
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::{blackbox::dh_mac::DhMacOracle, dh::PrivateKey, ec::Curve};
+
+    #[test]
+    fn finds_small_factors() {
+        let params = Parameters::default();
+        let factors = small_factors(&params, 1 << 16);
+        assert_eq!(
+            vec![
+                2, 3, 5, 109, 7963, 8539, 20641, 38833, 39341, 46337, 51977, 54319, 57529
+            ],
+            factors
+        );
+    }
+
+    #[test]
+    fn fails_when_factors_fall_short() {
+        let mut rng = StdRng::seed_from_u64(37);
+        let params = Parameters::default();
+        let mut oracle = DhMacOracle::new(params.clone());
+        assert!(recover_key(&mut oracle, &params, 1000, &mut rng).is_err());
+    }
+
+    #[test]
+    fn s8c57_small_subgroup_confinement() -> Result<()> {
+        let mut rng = StdRng::seed_from_u64(37);
+        let params = Parameters::default();
+        let key = PrivateKey::generate_with_rng(params.clone(), &mut rng);
+        let mut oracle = DhMacOracle::init(key.clone());
+
+        let res = recover_key(&mut oracle, &params, 1 << 16, &mut rng)?;
+        assert_eq!(key.x, res);
+        Ok(())
+    }
+
+    #[test]
+    fn kangaroo_finishes_residues_on_curves() -> Result<()> {
+        let mut rng = StdRng::seed_from_u64(37);
+        let curve = Curve::challenge_59();
+        let d = random_below(&curve.q, &mut rng);
+        let point = curve.scale(&curve.g, &d);
+
+        let r = (&curve.q >> 16) + 1u32;
+        let res = kangaroo_from_residue(&curve, &point, &(&d % &r), &r)?;
+        assert_eq!(d, res);
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "slow"]
+    fn s8c58_kangaroo_key_recovery() -> Result<()> {
+        let mut rng = StdRng::seed_from_u64(37);
+        let params = Parameters::challenge_58();
+        let key = PrivateKey::generate_with_rng(params, &mut rng);
+        let mut oracle = DhMacOracle::init(key.clone());
+
+        let res = recover_key_with_kangaroo(&mut oracle, &key.public_key(), 1 << 20, &mut rng)?;
+        assert_eq!(key.x, res);
+        Ok(())
+    }
+}
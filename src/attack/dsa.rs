@@ -0,0 +1,3 @@
+pub mod nonce;
+pub mod parameter_tampering;
+pub mod repeated_nonce;
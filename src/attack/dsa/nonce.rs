@@ -0,0 +1,73 @@
+use std::ops::Range;
+
+use num_bigint::BigUint;
+
+use crate::{
+    Data,
+    dsa::{PublicKey, Signature, hash, recover_private_key},
+};
+
+/// Recovers the private key behind `signature` by trying every nonce in `range`, stepping
+/// `g^k mod p` incrementally rather than exponentiating from scratch for each candidate.
+#[must_use]
+pub fn brute_force_nonce(
+    key: &PublicKey,
+    message: &Data,
+    signature: &Signature,
+    range: Range<u64>,
+) -> Option<BigUint> {
+    let params = &key.params;
+    let h = hash(message);
+    let mut gk = params.g.modpow(&BigUint::from(range.start), &params.p);
+
+    for k in range {
+        if &gk % &params.q == signature.r {
+            let k = BigUint::from(k);
+            let x = recover_private_key(params, &h, signature, &k)?;
+            if params.g.modpow(&x, &params.p) == key.y {
+                return Some(x);
+            }
+        }
+
+        gk = (gk * &params.g) % &params.p;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::{IntoDiagnostic, Result};
+    use num_traits::Num;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::dsa::{Parameters, fingerprint};
+
+    #[test]
+    fn s6c43_dsa_key_recovery_from_nonce() -> Result<()> {
+        let y = BigUint::from_str_radix(
+            "84ad4719d044495496a3201c8ff484feb45b962e7302e56a392aee4abab3e4bdebf2955b4736012f21a08084056b19bcd7fee56048e004e44984e2f411788efdc837a0d2e5abb7b555039fd243ac01f0fb2ed1dec568280ce678e931868d23eb095fde9d3779191b8c0299d6e07bbb283e6633451e535c45513b2d33c99ea17",
+            16,
+        )
+        .into_diagnostic()?;
+        let key = PublicKey::new(Parameters::default(), y);
+        let message = Data::from(
+            "For those that envy a MC it can be hazardous to your health\nSo be friendly, a matter of life and death, just like a etch-a-sketch\n".as_bytes(),
+        );
+        assert_eq!(
+            "d2d0714f014a9784047eaeccf956520045c45265",
+            hash(&message).to_str_radix(16)
+        );
+
+        let signature: Signature = "r = 548099063082341131477253921760299949438196259240\ns = 857042759984254168557880549501802188789837994940\n".parse()?;
+
+        let x = brute_force_nonce(&key, &message, &signature, 0..1 << 16).unwrap();
+        assert_eq!(
+            "0954edd5e0afe5542a4adf012611a91912a3ec16",
+            fingerprint(&x).hex()
+        );
+
+        Ok(())
+    }
+}
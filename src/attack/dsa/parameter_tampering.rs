@@ -0,0 +1,81 @@
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use crate::{
+    dsa::{Lenient, Parameters, PublicKey, Signature},
+    math::invmod,
+};
+
+/// Swaps the generator of `params` for an attacker-chosen one.
+#[must_use]
+pub fn tamper(params: &Parameters, g: BigUint) -> Parameters {
+    Parameters::new(params.p.clone(), params.q.clone(), g)
+}
+
+/// With `g = 0`, `v` is always zero, so a lenient verifier accepts `r = 0` with any `s` for any
+/// message.
+#[must_use]
+pub fn zero_generator_signature() -> Signature {
+    Signature::new(BigUint::zero(), BigUint::one())
+}
+
+/// With `g = p + 1`, `g^u1 = 1 mod p`, so `r = (y^z mod p) mod q` and `s = r / z mod q` verify
+/// against any message for any `z`.
+#[must_use]
+pub fn magic_signature(key: &PublicKey<Lenient>, z: &BigUint) -> Option<Signature> {
+    let Parameters { p, q, .. } = &key.params;
+
+    let r = key.y.modpow(z, p) % q;
+    let s = (&r * invmod(z, q)?) % q;
+    Some(Signature::new(r, s))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use super::*;
+    use crate::{
+        Data,
+        dsa::{PrivateKey, Strict},
+    };
+
+    #[test]
+    fn s6c45_dsa_parameter_tampering_g_0() -> Result<()> {
+        let params = tamper(&Parameters::default(), BigUint::zero());
+        assert!(PrivateKey::<Strict>::generate(params.clone()).is_err());
+
+        let key = PrivateKey::<Lenient>::generate(params)?;
+        let signature = key.sign(&Data::from("Hello, world".as_bytes()));
+        assert!(signature.r.is_zero());
+
+        let public = key.public_key();
+        let strict = PublicKey::<Strict>::new(public.params.clone(), public.y.clone());
+        for message in ["Hello, world", "Goodbye, world"] {
+            let message = Data::from(message.as_bytes());
+            assert!(public.verify(&message, &signature));
+            assert!(public.verify(&message, &zero_generator_signature()));
+            assert!(!strict.verify(&message, &signature));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn s6c45_dsa_parameter_tampering_g_p_plus_1() -> Result<()> {
+        let key: PrivateKey = PrivateKey::generate(Parameters::default())?;
+        let params = tamper(&key.params, &key.params.p + 1u32);
+
+        let public = PublicKey::<Lenient>::new(params.clone(), key.y.clone());
+        let strict = PublicKey::<Strict>::new(params, key.y.clone());
+        let signature = magic_signature(&public, &BigUint::from(12345u32)).unwrap();
+
+        for message in ["Hello, world", "Goodbye, world"] {
+            let message = Data::from(message.as_bytes());
+            assert!(public.verify(&message, &signature));
+            assert!(!strict.verify(&message, &signature));
+        }
+
+        Ok(())
+    }
+}
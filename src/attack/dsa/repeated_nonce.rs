@@ -0,0 +1,96 @@
+use itertools::Itertools;
+use num_bigint::BigUint;
+
+use crate::{
+    dsa::{Parameters, PublicKey, SignedMessage, recover_private_key},
+    math::invmod,
+};
+
+/// Finds pairs of signatures that share a nonce. Equal nonces give equal `r`, so grouping on `r`
+/// finds them in a single pass instead of comparing every pair.
+#[must_use]
+pub fn find_repeated_nonces(messages: &[SignedMessage]) -> Vec<(&SignedMessage, &SignedMessage)> {
+    messages
+        .iter()
+        .into_group_map_by(|message| &message.signature.r)
+        .into_values()
+        .filter_map(|group| {
+            let first = group[0];
+            // identical signatures over the same message say nothing about `k`
+            let second = group
+                .into_iter()
+                .find(|message| message.signature.s != first.signature.s)?;
+            Some((first, second))
+        })
+        .collect()
+}
+
+/// Recovers `k = (m1 - m2) / (s1 - s2) mod q` from two signatures made with the same nonce.
+#[must_use]
+pub fn recover_nonce(
+    params: &Parameters,
+    lhs: &SignedMessage,
+    rhs: &SignedMessage,
+) -> Option<BigUint> {
+    let q = &params.q;
+    let dm = (&lhs.hash % q + q - &rhs.hash % q) % q;
+    let ds = (&lhs.signature.s + q - &rhs.signature.s) % q;
+    let k = (dm * invmod(&ds, q)?) % q;
+    Some(k)
+}
+
+/// Recovers the private key for `key` from any pair of signatures in `messages` that reuse a
+/// nonce, checking each candidate against the public key.
+#[must_use]
+pub fn repeated_nonce_attack(key: &PublicKey, messages: &[SignedMessage]) -> Option<BigUint> {
+    let params = &key.params;
+
+    find_repeated_nonces(messages)
+        .into_iter()
+        .filter_map(|(lhs, rhs)| {
+            let k = recover_nonce(params, lhs, rhs)?;
+            recover_private_key(params, &lhs.hash, &lhs.signature, &k)
+        })
+        .find(|x| params.g.modpow(x, &params.p) == key.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+    use rand::seq::IndexedRandom;
+
+    use super::*;
+    use crate::{
+        Data,
+        dsa::{PrivateKey, fingerprint},
+        math::random_below,
+    };
+
+    #[test]
+    fn s6c44_dsa_nonce_recovery_from_repeated_nonce() -> Result<()> {
+        let key: PrivateKey = PrivateKey::generate(Parameters::default())?;
+        let mut rng = rand::rng();
+
+        // a signer with a broken RNG that only ever picks from a handful of nonces
+        let nonces: Vec<BigUint> = (0..100)
+            .map(|_| random_below(&key.params.q, &mut rng))
+            .collect();
+        let input: String = (0..1000)
+            .map(|i| {
+                let message = format!("message number {i} ");
+                let k = nonces.choose(&mut rng).unwrap();
+                let signature = key.sign_with_k(&Data::from(message.as_bytes()), k).unwrap();
+                format!("msg: {message}\ns: {}\nr: {}\n", signature.s, signature.r)
+            })
+            .collect();
+
+        let messages = SignedMessage::parse_all(&input)?;
+        assert_eq!(1000, messages.len());
+
+        let x = repeated_nonce_attack(&key.public_key(), &messages).unwrap();
+        assert_eq!(fingerprint(&key.x).hex(), fingerprint(&x).hex());
+
+        Ok(())
+    }
+}
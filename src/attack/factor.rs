@@ -0,0 +1,289 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::One;
+
+/// The result of a factoring method that may run out of budget before it finishes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partial<T> {
+    pub value: T,
+    pub iterations: u64,
+    /// Whether the budget ran out before the method could finish.
+    pub exhausted: bool,
+}
+
+impl<T> Partial<T> {
+    fn done(value: T, iterations: u64) -> Self {
+        Self {
+            value,
+            iterations,
+            exhausted: false,
+        }
+    }
+
+    fn exhausted(value: T, iterations: u64) -> Self {
+        Self {
+            value,
+            iterations,
+            exhausted: true,
+        }
+    }
+}
+
+/// Fermat's method: searches upwards from `sqrt(n)` for `a` with `a^2 - n` square, which finds
+/// `n = (a - b)(a + b)` quickly when the factors are close together.
+#[must_use]
+pub fn fermat(n: &BigUint, budget: u64) -> Partial<Option<(BigUint, BigUint)>> {
+    if n.is_even() {
+        let factors = (n > &BigUint::from(2u32)).then(|| (BigUint::from(2u32), n >> 1));
+        return Partial::done(factors, 0);
+    }
+
+    let mut a = n.sqrt();
+    if &a * &a < *n {
+        a += 1u32;
+    }
+
+    for i in 0..budget {
+        let b2 = &a * &a - n;
+        let b = b2.sqrt();
+        if &b * &b == b2 {
+            let p = &a - &b;
+            let q = &a + &b;
+            let factors = (!p.is_one()).then_some((p, q));
+            return Partial::done(factors, i + 1);
+        }
+
+        a += 1u32;
+    }
+
+    Partial::exhausted(None, budget)
+}
+
+/// Pollard's rho with Floyd cycle detection on `x -> x^2 + c`, retrying with the next `c` whenever
+/// a cycle collapses onto `n` itself.
+#[must_use]
+pub fn pollard_rho(n: &BigUint, budget: u64) -> Partial<Option<BigUint>> {
+    if n.is_even() {
+        let factor = (n > &BigUint::from(2u32)).then(|| BigUint::from(2u32));
+        return Partial::done(factor, 0);
+    }
+
+    let mut iterations = 0;
+    let mut c = BigUint::one();
+
+    while iterations < budget {
+        let f = |x: &BigUint| (x * x + &c) % n;
+        let mut x = BigUint::from(2u32);
+        let mut y = x.clone();
+
+        while iterations < budget {
+            iterations += 1;
+            x = f(&x);
+            y = f(&f(&y));
+
+            let diff = if x > y { &x - &y } else { &y - &x };
+            let d = diff.gcd(n);
+            if &d == n {
+                break;
+            }
+            if !d.is_one() {
+                return Partial::done(Some(d), iterations);
+            }
+        }
+
+        c += 1u32;
+    }
+
+    Partial::exhausted(None, budget)
+}
+
+/// Pollard's p - 1: raises `2` to every prime power up to `bound`, which finds `p` whenever `p - 1`
+/// is `bound`-smooth. Each prime costs one iteration.
+#[must_use]
+pub fn pollard_p_minus_1(n: &BigUint, bound: u64, budget: u64) -> Partial<Option<BigUint>> {
+    let mut a = BigUint::from(2u32);
+
+    for (i, p) in primes(bound).enumerate() {
+        let i = i as u64;
+        if i >= budget {
+            return Partial::exhausted(None, budget);
+        }
+
+        let mut pk = p;
+        while pk * p <= bound {
+            pk *= p;
+        }
+        a = a.modpow(&BigUint::from(pk), n);
+
+        let d = (&a + n - 1u32).gcd(n);
+        if &d == n {
+            // every factor became smooth at once, so there is nothing left to separate
+            return Partial::done(None, i + 1);
+        }
+        if !d.is_one() {
+            return Partial::done(Some(d), i + 1);
+        }
+    }
+
+    Partial::done(None, primes(bound).count() as u64)
+}
+
+pub(crate) fn primes(bound: u64) -> impl Iterator<Item = u64> {
+    let len = usize::try_from(bound).expect("bound fits in memory") + 1;
+    let mut sieve = vec![true; len];
+
+    (2..len).filter_map(move |i| {
+        if !sieve[i] {
+            return None;
+        }
+
+        for j in (i * i..len).step_by(i) {
+            sieve[j] = false;
+        }
+        Some(i as u64)
+    })
+}
+
+/// Bernstein's batch GCD: finds every modulus that shares a factor with another using a product
+/// tree and a remainder tree, rather than comparing all pairs. Each tree level and each final GCD
+/// costs one iteration; if the budget runs out during the final GCDs, the moduli reached so far
+/// still get their results.
+#[must_use]
+pub fn batch_gcd(moduli: &[BigUint], budget: u64) -> Partial<Vec<Option<BigUint>>> {
+    let mut iterations = 0;
+    let mut res = vec![None; moduli.len()];
+    if moduli.is_empty() {
+        return Partial::done(res, 0);
+    }
+
+    let mut tree = vec![moduli.to_vec()];
+    while tree[tree.len() - 1].len() > 1 {
+        if iterations >= budget {
+            return Partial::exhausted(res, iterations);
+        }
+
+        let level = tree[tree.len() - 1]
+            .chunks(2)
+            .map(|pair| pair.iter().product())
+            .collect();
+        tree.push(level);
+        iterations += 1;
+    }
+
+    let root = tree[tree.len() - 1][0].clone();
+    let mut remainders = vec![root];
+    for level in tree.iter().rev().skip(1) {
+        if iterations >= budget {
+            return Partial::exhausted(res, iterations);
+        }
+
+        remainders = level
+            .iter()
+            .enumerate()
+            .map(|(i, n)| &remainders[i / 2] % (n * n))
+            .collect();
+        iterations += 1;
+    }
+
+    for (i, (n, r)) in moduli.iter().zip(remainders).enumerate() {
+        if iterations >= budget {
+            return Partial::exhausted(res, iterations);
+        }
+
+        iterations += 1;
+        let d = (r / n).gcd(n);
+        if !d.is_one() {
+            res[i] = Some(d);
+        }
+    }
+
+    Partial::done(res, iterations)
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use pretty_assertions::assert_eq;
+    use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+    use super::*;
+    use crate::math::prime::{gen_prime, is_probable_prime};
+
+    #[test]
+    fn fermat_factors_close_primes() {
+        let mut rng = StdRng::seed_from_u64(37);
+        let p = gen_prime(512, &mut rng);
+        let mut q = &p + 2u32;
+        while !is_probable_prime(&q, &mut rng) {
+            q += 2u32;
+        }
+
+        let res = fermat(&(&p * &q), 10);
+        assert_eq!(Some((p, q)), res.value);
+        assert!(!res.exhausted);
+    }
+
+    #[test]
+    fn pollard_rho_factors_small_semiprimes() {
+        let mut rng = StdRng::seed_from_u64(37);
+        let p = gen_prime(32, &mut rng);
+        let q = gen_prime(64, &mut rng);
+
+        let res = pollard_rho(&(&p * &q), 1 << 20);
+        assert!(res.value.is_some_and(|d| d == p || d == q));
+
+        let res = pollard_rho(&(&p * &q), 1);
+        assert!(res.exhausted);
+    }
+
+    #[test]
+    fn pollard_p_minus_1_factors_smooth_primes() {
+        let mut rng = StdRng::seed_from_u64(37);
+        let mut small = primes(1000).skip(1).collect_vec();
+        let p = loop {
+            // distinct primes, so that p - 1 is powersmooth as well as smooth
+            small.shuffle(&mut rng);
+            let m = small
+                .iter()
+                .scan(BigUint::from(2u32), |m, &p| {
+                    *m *= p;
+                    Some(m.clone())
+                })
+                .find(|m| m.bits() >= 256)
+                .unwrap();
+            if is_probable_prime(&(&m + 1u32), &mut rng) {
+                break m + 1u32;
+            }
+        };
+        let q = gen_prime(256, &mut rng);
+        let n = &p * &q;
+
+        let res = pollard_p_minus_1(&n, 1000, 1000);
+        assert_eq!(Some(p), res.value);
+
+        let res = pollard_p_minus_1(&n, 1000, 10);
+        assert!(res.exhausted);
+    }
+
+    #[test]
+    fn batch_gcd_finds_shared_factors() {
+        let mut rng = StdRng::seed_from_u64(37);
+        let shared = gen_prime(128, &mut rng);
+        let mut moduli = (0..8)
+            .map(|_| gen_prime(128, &mut rng) * gen_prime(128, &mut rng))
+            .collect_vec();
+        moduli[2] = &shared * gen_prime(128, &mut rng);
+        moduli[5] = &shared * gen_prime(128, &mut rng);
+
+        let res = batch_gcd(&moduli, 100);
+        let expected = (0..8)
+            .map(|i| [2, 5].contains(&i).then(|| shared.clone()))
+            .collect_vec();
+        assert_eq!(expected, res.value);
+
+        let res = batch_gcd(&moduli, 9);
+        assert!(res.exhausted);
+        assert_eq!(expected[..3], res.value[..3]);
+        assert_eq!(None, res.value[5]);
+    }
+}
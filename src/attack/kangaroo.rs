@@ -0,0 +1,175 @@
+use std::hash::{BuildHasher, BuildHasherDefault, DefaultHasher};
+
+use num_bigint::BigUint;
+
+use crate::group::Group;
+
+/// The pseudorandom jump function: an element hashing to `i` jumps by `2^(i mod k)`, and the tame
+/// kangaroo makes `n` jumps before setting its trap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Jumps {
+    pub k: u32,
+    pub n: u64,
+}
+
+impl Jumps {
+    #[must_use]
+    pub fn new(k: u32, n: u64) -> Self {
+        Self { k, n }
+    }
+
+    /// Picks `k` so that the mean jump is around the square root of `width`, and `n` as four
+    /// times the mean jump.
+    #[must_use]
+    pub fn for_width(width: u64) -> Self {
+        let k = (width.checked_ilog2().unwrap_or(0) / 2 + 2).min(63);
+        let mean = ((1u64 << k) - 1) / u64::from(k);
+        Self::new(k, 4 * mean)
+    }
+
+    fn index<T: std::hash::Hash>(self, element: &T) -> usize {
+        let hash = BuildHasherDefault::<DefaultHasher>::default().hash_one(element);
+        (hash % u64::from(self.k)) as usize
+    }
+}
+
+/// Pollard's kangaroo: finds `x` in `[a, b]` with `g^x = y`. A tame kangaroo jumps away from
+/// `g^b` and leaves a trap where it lands; a wild kangaroo starting from `y` follows the same jump
+/// function, and falls in the trap if its path ever meets the tame one. Each jump costs a group
+/// operation, around `sqrt(b - a)` of them when the jumps are well tuned. Returns [`None`] if the
+/// wild kangaroo overshoots, which happens either when `x` is out of range or by bad luck, and
+/// also when `k` isn't in `1..64` or the distances wouldn't fit in a `u64`.
+#[must_use]
+pub fn kangaroo<G: Group>(
+    group: &G,
+    g: &G::Element,
+    y: &G::Element,
+    a: &BigUint,
+    b: &BigUint,
+    jumps: Jumps,
+) -> Option<BigUint> {
+    if !(1..64).contains(&jumps.k) || b < a {
+        return None;
+    }
+    let width = u64::try_from(b - a).ok()?;
+
+    let steps = (0..jumps.k)
+        .scan(g.clone(), |step, _| {
+            let res = step.clone();
+            *step = group.combine(step, step);
+            Some(res)
+        })
+        .collect::<Vec<_>>();
+
+    let mut tame_distance = 0u64;
+    let mut tame = group.scale(g, b);
+    for _ in 0..jumps.n {
+        let i = jumps.index(&tame);
+        tame_distance = tame_distance.checked_add(1 << i)?;
+        tame = group.combine(&tame, &steps[i]);
+    }
+
+    let limit = width.checked_add(tame_distance)?;
+    let mut wild_distance = 0u64;
+    let mut wild = y.clone();
+    while wild_distance <= limit {
+        if wild == tame {
+            return Some(b + tame_distance - wild_distance);
+        }
+
+        let i = jumps.index(&wild);
+        wild_distance = wild_distance.checked_add(1 << i)?;
+        wild = group.combine(&wild, &steps[i]);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::{Num, Zero};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{dh::Parameters, ec::Curve};
+
+    #[test]
+    fn finds_logs_in_range() {
+        let params = Parameters::challenge_58();
+        let a = BigUint::from(1000u32);
+        let b = BigUint::from(1u32 << 16);
+        let x = BigUint::from(31337u32);
+        let y = params.g.modpow(&x, &params.p);
+
+        let res = kangaroo(&params, &params.g, &y, &a, &b, Jumps::for_width(1 << 16));
+        assert_eq!(Some(x), res);
+
+        let y = params.g.modpow(&BigUint::from(999u32), &params.p);
+        let res = kangaroo(&params, &params.g, &y, &a, &b, Jumps::for_width(1 << 16));
+        assert_eq!(None, res);
+    }
+
+    #[test]
+    fn rejects_bad_parameters() {
+        let params = Parameters::challenge_58();
+        let (a, b) = (BigUint::zero(), BigUint::from(1000u32));
+        let y = params.g.clone();
+
+        assert_eq!(
+            None,
+            kangaroo(&params, &params.g, &y, &a, &b, Jumps::new(0, 10))
+        );
+        assert_eq!(
+            None,
+            kangaroo(&params, &params.g, &y, &a, &b, Jumps::new(64, 10))
+        );
+        assert_eq!(
+            None,
+            kangaroo(&params, &params.g, &y, &b, &a, Jumps::for_width(0))
+        );
+
+        let wide = BigUint::from(u128::MAX);
+        assert_eq!(
+            None,
+            kangaroo(&params, &params.g, &y, &a, &wide, Jumps::for_width(0))
+        );
+
+        let long = Jumps::new(63, u64::MAX);
+        assert_eq!(None, kangaroo(&params, &params.g, &y, &a, &b, long));
+    }
+
+    #[test]
+    fn finds_logs_on_curves() {
+        let curve = Curve::challenge_59();
+        let x = BigUint::from(54321u32);
+        let y = curve.scale(&curve.g, &x);
+
+        let b = BigUint::from(100_000u32);
+        let res = kangaroo(
+            &curve,
+            &curve.g,
+            &y,
+            &BigUint::zero(),
+            &b,
+            Jumps::for_width(100_000),
+        );
+        assert_eq!(Some(x), res);
+    }
+
+    #[test]
+    fn s8c58_kangaroo() {
+        let params = Parameters::challenge_58();
+        let y = BigUint::from_str_radix("7760073848032689505395005705677365876654629189298052775754597607446617558600394076764814236081991643094239886772481052254010323780165093955236429914607119", 10).unwrap();
+
+        let b = BigUint::from(1u32 << 20);
+        let res = kangaroo(
+            &params,
+            &params.g,
+            &y,
+            &BigUint::zero(),
+            &b,
+            Jumps::for_width(1 << 20),
+        );
+        assert_eq!(Some(BigUint::from(705_485u32)), res);
+    }
+}
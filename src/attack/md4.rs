@@ -0,0 +1,260 @@
+use rand::Rng;
+
+use crate::{
+    Data,
+    hash::md4::{INITIAL_STATE, compress, message_word, states, step},
+};
+
+/// A sufficient condition on one bit of a register value. Bits are numbered from 1, as in Wang et
+/// al.; comparisons are against the value computed `n` steps earlier.
+#[derive(Debug, Clone, Copy)]
+enum Condition {
+    Zero(u32),
+    One(u32),
+    Eq(u32, usize),
+}
+
+use Condition::{Eq, One, Zero};
+
+impl Condition {
+    fn bit(self) -> u32 {
+        match self {
+            Zero(bit) | One(bit) | Eq(bit, _) => bit - 1,
+        }
+    }
+
+    /// The value the bit should have, given the register values up to step `i`.
+    fn target(self, q: &[u32], i: usize) -> bool {
+        match self {
+            Zero(_) => false,
+            One(_) => true,
+            Eq(bit, back) => q[i - back] >> (bit - 1) & 1 == 1,
+        }
+    }
+
+    fn holds(self, q: &[u32], i: usize) -> bool {
+        (q[i] >> self.bit() & 1 == 1) == self.target(q, i)
+    }
+}
+
+/// Conditions on `a1, d1, c1, b1, ..., b4`.
+const ROUND_1: [&[Condition]; 16] = [
+    &[Eq(7, 1)],
+    &[Zero(7), Eq(8, 1), Eq(11, 1)],
+    &[One(7), One(8), Zero(11), Eq(26, 1)],
+    &[One(7), Zero(8), Zero(11), Zero(26)],
+    &[One(8), One(11), Zero(26), Eq(14, 1)],
+    &[
+        Zero(14),
+        Eq(19, 1),
+        Eq(20, 1),
+        Eq(21, 1),
+        Eq(22, 1),
+        One(26),
+    ],
+    &[
+        Eq(13, 1),
+        Zero(14),
+        Eq(15, 1),
+        Zero(19),
+        Zero(20),
+        One(21),
+        Zero(22),
+    ],
+    &[
+        One(13),
+        One(14),
+        Zero(15),
+        Eq(17, 1),
+        Zero(19),
+        Zero(20),
+        Zero(21),
+        Zero(22),
+    ],
+    &[
+        One(13),
+        One(14),
+        One(15),
+        Zero(17),
+        Zero(19),
+        Zero(20),
+        Zero(21),
+        Eq(23, 1),
+        One(22),
+        Eq(26, 1),
+    ],
+    &[
+        One(13),
+        One(14),
+        One(15),
+        Zero(17),
+        Zero(20),
+        One(21),
+        One(22),
+        Zero(23),
+        One(26),
+        Eq(30, 1),
+    ],
+    &[
+        One(17),
+        Zero(20),
+        Zero(21),
+        Zero(22),
+        Zero(23),
+        Zero(26),
+        One(30),
+        Eq(32, 1),
+    ],
+    &[
+        Zero(20),
+        One(21),
+        One(22),
+        Eq(23, 1),
+        One(26),
+        Zero(30),
+        Zero(32),
+    ],
+    &[Zero(23), Zero(26), Eq(27, 1), Eq(29, 1), One(30), Zero(32)],
+    &[Zero(23), Zero(26), One(27), One(29), Zero(30), One(32)],
+    &[Eq(19, 1), One(23), One(26), Zero(27), Zero(29), Zero(30)],
+    &[Zero(19), One(26), One(27), One(29), Zero(30)],
+];
+
+/// Conditions on `a5`, compared against `c4` two steps back.
+const A5: &[Condition] = &[Eq(19, 2), One(26), Zero(27), One(29), One(32)];
+
+/// Conditions on `d5`, compared against `a5` and `b4`.
+const D5: &[Condition] = &[Eq(19, 1), Eq(26, 2), Eq(27, 2), Eq(29, 2), Eq(32, 2)];
+
+/// Index into the register values of the value computed by step `i`.
+const fn at(i: usize) -> usize {
+    i + 4
+}
+
+fn prev(q: &[u32], i: usize) -> [u32; 4] {
+    q[i..i + 4].try_into().expect("four registers")
+}
+
+/// Recomputes the message words for round 1 steps `range`, so that the register values stay put.
+fn solve_words(q: &[u32], block: &mut [u32; 16], steps: std::ops::Range<usize>) {
+    for i in steps {
+        block[i] = message_word(i, prev(q, i), q[at(i)]);
+    }
+}
+
+/// The differential of Wang et al.: `m1 + 2^31`, `m2 + 2^31 - 2^28`, `m12 - 2^16`.
+#[must_use]
+pub fn differential(block: &[u32; 16]) -> [u32; 16] {
+    let mut res = *block;
+    res[1] = res[1].wrapping_add(1 << 31);
+    res[2] = res[2].wrapping_add(1 << 31).wrapping_sub(1 << 28);
+    res[12] = res[12].wrapping_sub(1 << 16);
+    res
+}
+
+/// Modifies a block so that every round 1 condition holds, then corrects `a5` and `d5` through
+/// `a1` and `a2`, re-solving the round 1 words so no earlier condition breaks.
+pub fn massage(block: &mut [u32; 16]) {
+    let mut q = states(INITIAL_STATE, block);
+
+    // single-step modification: force each round 1 value's bits, then solve for its word
+    for (i, conditions) in ROUND_1.iter().enumerate() {
+        let mut value = step(i, prev(&q, i), block);
+        for &condition in *conditions {
+            q[at(i)] = value;
+            let mask = 1 << condition.bit();
+            if condition.target(&q, at(i)) {
+                value |= mask;
+            } else {
+                value &= !mask;
+            }
+        }
+        q[at(i)] = value;
+        block[i] = message_word(i, prev(&q, i), value);
+    }
+
+    // a5 takes m0 with the same rotation as a1, so flipping a bit of a1 flips it in a5 too
+    for &condition in A5 {
+        q[at(16)] = step(16, prev(&q, 16), block);
+        if !condition.holds(&q, at(16)) {
+            q[at(0)] ^= 1 << condition.bit();
+            solve_words(&q, block, 0..5);
+        }
+    }
+    q[at(16)] = step(16, prev(&q, 16), block);
+
+    // d5 takes m4, rotated by 5 rather than a2's 3, so the matching bit of a2 is two lower
+    for &condition in D5 {
+        q[at(17)] = step(17, prev(&q, 17), block);
+        if !condition.holds(&q, at(17)) {
+            q[at(4)] ^= 1 << (condition.bit() - 2);
+            solve_words(&q, block, 4..9);
+        }
+    }
+}
+
+/// A pair of distinct one-block messages with the same MD4 hash.
+#[derive(Debug, Clone)]
+pub struct Md4Collision {
+    pub messages: [Data; 2],
+    pub attempts: u64,
+}
+
+/// Massages random blocks until one collides with its differential partner.
+#[must_use]
+pub fn wang_collision<R: Rng + ?Sized>(rng: &mut R) -> Md4Collision {
+    for attempts in 1.. {
+        let mut block: [u32; 16] = rng.random();
+        massage(&mut block);
+        let other = differential(&block);
+
+        if compress(INITIAL_STATE, &block) == compress(INITIAL_STATE, &other) {
+            return Md4Collision {
+                messages: [
+                    crate::hash::md4::block(&block),
+                    crate::hash::md4::block(&other),
+                ],
+                attempts,
+            };
+        }
+    }
+
+    unreachable!("the search only ends on a collision")
+}
+
+#[cfg(test)]
+mod tests {
+    use md4::{Digest, Md4};
+    use pretty_assertions::assert_eq;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::hash::md4::md4;
+
+    #[test]
+    fn massage_satisfies_conditions() {
+        let mut rng = StdRng::seed_from_u64(55);
+        for _ in 0..100 {
+            let mut block: [u32; 16] = rng.random();
+            massage(&mut block);
+
+            let q = states(INITIAL_STATE, &block);
+            for (i, conditions) in ROUND_1.iter().enumerate() {
+                assert!(conditions.iter().all(|c| c.holds(&q, at(i))));
+            }
+            assert!(A5.iter().all(|c| c.holds(&q, at(16))));
+            assert!(D5.iter().all(|c| c.holds(&q, at(17))));
+        }
+    }
+
+    #[test]
+    fn s7c55_md4_collisions() {
+        let mut rng = StdRng::seed_from_u64(55);
+        let res = wang_collision(&mut rng);
+
+        let [a, b] = &res.messages;
+        assert!(a != b);
+        assert_eq!(md4(a), md4(b));
+        assert_eq!(Md4::digest(a), Md4::digest(b));
+    }
+}
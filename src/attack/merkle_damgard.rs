@@ -0,0 +1,3 @@
+pub mod herding;
+pub mod joux;
+pub mod second_preimage;
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use crate::{
+    Data, Error, Result,
+    attack::merkle_damgard::joux::find_collision_between,
+    error::InvalidLengthType,
+    hash::{Compress, MerkleDamgard, merkle_damgard::BLOCK_SIZE},
+};
+
+/// A binary tree of collisions funnelling `2^k` random leaf states into a single root. Each node
+/// keeps the block that takes it to its parent.
+#[derive(Debug, Clone)]
+pub struct Diamond {
+    pub levels: Vec<Vec<(Data, Data)>>,
+    pub root: Data,
+    /// Compression calls spent building the tree.
+    pub calls: u64,
+}
+
+impl Diamond {
+    pub fn new<C: Compress>(md: &MerkleDamgard<C>, k: u32) -> Result<Self> {
+        let start = md.calls();
+        let mut rng = rand::rng();
+
+        let mut states: Vec<Data> = (0..1usize << k)
+            .map(|_| {
+                let state: Box<[u8]> = (0..md.state_len()).map(|_| rng.random()).collect();
+                Data::from(state)
+            })
+            .collect();
+
+        let mut levels = vec![];
+        while states.len() > 1 {
+            let mut level = vec![];
+            let mut next = vec![];
+            for pair in states.chunks_exact(2) {
+                let ([a, b], state) = find_collision_between(md, [&pair[0], &pair[1]])?;
+                level.push((pair[0].clone(), a));
+                level.push((pair[1].clone(), b));
+                next.push(state);
+            }
+
+            levels.push(level);
+            states = next;
+        }
+
+        Ok(Self {
+            levels,
+            root: states.remove(0),
+            calls: md.calls() - start,
+        })
+    }
+
+    pub fn leaves(&self) -> impl Iterator<Item = &Data> {
+        self.levels
+            .first()
+            .into_iter()
+            .flatten()
+            .map(|(state, _)| state)
+    }
+
+    /// The blocks leading from leaf `i` up to the root.
+    #[must_use]
+    pub fn path(&self, mut i: usize) -> Data {
+        let mut bytes = vec![];
+        for level in &self.levels {
+            bytes.extend(level[i].1.iter());
+            i /= 2;
+        }
+        Data::from(bytes)
+    }
+}
+
+/// A committed hash for any message of `prefix_blocks` blocks, followed by a linking block and
+/// a path through the diamond.
+#[derive(Debug, Clone)]
+pub struct Prediction {
+    pub hash: Data,
+    pub diamond: Diamond,
+    pub prefix_blocks: usize,
+}
+
+/// A message matching a [`Prediction`], and the compression calls spent linking it.
+#[derive(Debug, Clone)]
+pub struct Herded {
+    pub message: Data,
+    pub calls: u64,
+}
+
+impl Prediction {
+    /// Builds the diamond and commits to its root, finished with the padding for the length every
+    /// herded message will have.
+    pub fn new<C: Compress>(md: &MerkleDamgard<C>, k: u32, prefix_blocks: usize) -> Result<Self> {
+        let diamond = Diamond::new(md, k)?;
+        let len = (prefix_blocks + 1 + diamond.levels.len()) * BLOCK_SIZE;
+        let hash = md.hash_from(&diamond.root, &[], len)?;
+
+        Ok(Self {
+            hash,
+            diamond,
+            prefix_blocks,
+        })
+    }
+
+    /// Pads `prefix` with spaces to the committed length and searches for a block linking it into
+    /// the diamond, which costs about `2^(b - k)` calls for a `b`-bit state.
+    pub fn herd<C: Compress>(&self, md: &MerkleDamgard<C>, prefix: &[u8]) -> Result<Herded> {
+        let len = self.prefix_blocks * BLOCK_SIZE;
+        if prefix.len() > len {
+            return Err(Error::InvalidLength {
+                kind: InvalidLengthType::Message,
+                expected: len,
+                actual: prefix.len(),
+            });
+        }
+
+        let start = md.calls();
+        let mut padded = prefix.to_vec();
+        padded.resize(len, b' ');
+
+        let leaves: HashMap<_, _> = self
+            .diamond
+            .leaves()
+            .enumerate()
+            .map(|(i, state)| (state.to_vec(), i))
+            .collect();
+        let state = md.iterate(md.initial_state(), &padded)?;
+
+        let mut rng = rand::rng();
+        let (link, leaf) = loop {
+            let block: [u8; BLOCK_SIZE] = rng.random();
+            let next = md.compress(&state, &block)?;
+            if let Some(&leaf) = leaves.get(&next.to_vec()) {
+                break (block, leaf);
+            }
+        };
+
+        padded.extend(link);
+        padded.extend(self.diamond.path(leaf).iter());
+        Ok(Herded {
+            message: Data::from(padded),
+            calls: md.calls() - start,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::hash::DaviesMeyer;
+
+    #[test]
+    fn diamond_paths_reach_root() -> Result<()> {
+        let md = MerkleDamgard::new(DaviesMeyer, [0u8; 2], true)?;
+        let diamond = Diamond::new(&md, 4)?;
+
+        assert_eq!(16, diamond.leaves().count());
+        for (i, leaf) in diamond.leaves().enumerate() {
+            assert_eq!(diamond.root, md.iterate(leaf, &diamond.path(i))?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn s7c54_kelsey_and_kohnos_nostradamus_attack() -> Result<()> {
+        let md = MerkleDamgard::new(DaviesMeyer, [0u8; 3], true)?;
+        let prediction = Prediction::new(&md, 6, 4)?;
+
+        for results in [
+            "Yankees 3, Red Sox 2; Mets 5, Phillies 1",
+            "Red Sox 7, Yankees 0; Phillies 2, Mets 1",
+        ] {
+            let herded = prediction.herd(&md, results.as_bytes())?;
+            assert!(herded.message.starts_with(results.as_bytes()));
+            assert_eq!(prediction.hash, md.hash(&herded.message)?);
+
+            // linking should be far cheaper than a 2^24 preimage search
+            assert!(herded.calls < 1 << 22);
+        }
+
+        assert!(prediction.diamond.calls > 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use rand::Rng;
+
+use crate::{
+    Data, Result,
+    hash::{Compress, MerkleDamgard, merkle_damgard::BLOCK_SIZE},
+};
+
+/// Finds two distinct blocks that take `state` to the same next state, by a birthday search over
+/// random blocks. Returns both blocks and the shared state.
+pub fn find_collision<C: Compress>(
+    md: &MerkleDamgard<C>,
+    state: &[u8],
+) -> Result<([Data; 2], Data)> {
+    find_collision_between(md, [state, state])
+}
+
+/// Finds a block for each of two states that takes both to the same next state, alternating
+/// random blocks from each side until one lands on a state the other side has already reached.
+pub fn find_collision_between<C: Compress>(
+    md: &MerkleDamgard<C>,
+    states: [&[u8]; 2],
+) -> Result<([Data; 2], Data)> {
+    let mut rng = rand::rng();
+    let mut seen = [HashMap::new(), HashMap::new()];
+
+    for side in [0, 1].into_iter().cycle() {
+        let block: [u8; BLOCK_SIZE] = rng.random();
+        let next = md.compress(states[side], &block)?;
+
+        if let Some(&other) = seen[1 - side].get(&next.to_vec())
+            && (states[0] != states[1] || other != block)
+        {
+            let mut blocks = [Data::from(other), Data::from(block)];
+            if side == 0 {
+                blocks.swap(0, 1);
+            }
+            return Ok((blocks, next));
+        }
+        seen[side].insert(next.to_vec(), block);
+        if states[0] == states[1] {
+            // from a single state, any two blocks will do
+            seen[1 - side].insert(next.to_vec(), block);
+        }
+    }
+
+    unreachable!("the search only ends on a collision")
+}
+
+/// `2^n` messages of `n` blocks each, all reaching the same state: each block can be either half
+/// of the collision found for the state before it.
+#[derive(Debug, Clone)]
+pub struct Multicollision {
+    pub pairs: Vec<[Data; 2]>,
+    pub state: Data,
+}
+
+impl Multicollision {
+    /// Joux's construction: `n` birthday searches, each starting from the state the last one
+    /// collided on.
+    pub fn new<C: Compress>(md: &MerkleDamgard<C>, state: &[u8], n: usize) -> Result<Self> {
+        let mut res = Self {
+            pairs: vec![],
+            state: Data::from(state),
+        };
+        for _ in 0..n {
+            res.extend(md)?;
+        }
+        Ok(res)
+    }
+
+    /// Doubles the number of messages with one more birthday search.
+    pub fn extend<C: Compress>(&mut self, md: &MerkleDamgard<C>) -> Result<()> {
+        let (pair, state) = find_collision(md, &self.state)?;
+        self.pairs.push(pair);
+        self.state = state;
+        Ok(())
+    }
+
+    /// The message whose `i`th block is picked by `choices[i]`, if there's a choice for every
+    /// block.
+    #[must_use]
+    pub fn message(&self, choices: &[bool]) -> Option<Data> {
+        if choices.len() != self.pairs.len() {
+            return None;
+        }
+
+        let bytes: Box<[u8]> = self
+            .pairs
+            .iter()
+            .zip(choices)
+            .flat_map(|(pair, &choice)| pair[usize::from(choice)].iter().copied())
+            .collect();
+        Some(Data::from(bytes))
+    }
+
+    /// Every colliding message, generated on demand.
+    pub fn messages(&self) -> impl Iterator<Item = Data> + '_ {
+        self.pairs
+            .iter()
+            .map(|pair| pair.iter())
+            .multi_cartesian_product()
+            .map(|blocks| {
+                let bytes: Box<[u8]> = blocks.into_iter().flat_map(|b| b.iter().copied()).collect();
+                Data::from(bytes)
+            })
+    }
+}
+
+/// Two distinct messages colliding under `f(m) || g(m)`, and the compression calls each hash made
+/// to find them.
+#[derive(Debug, Clone)]
+pub struct CascadeCollision {
+    pub messages: [Data; 2],
+    pub f_calls: u64,
+    pub g_calls: u64,
+}
+
+/// Collides the cascade of a cheap hash `f` and a stronger hash `g`. A `2^(b/2)` multicollision in
+/// `f`, where `b` is the bit size of `g`, likely contains a birthday collision in `g`; if not, the
+/// multicollision grows until it does.
+pub fn cascade_collision<F: Compress, G: Compress>(
+    f: &MerkleDamgard<F>,
+    g: &MerkleDamgard<G>,
+) -> Result<CascadeCollision> {
+    let (f_start, g_start) = (f.calls(), g.calls());
+
+    let n = g.state_len() * 8 / 2;
+    let mut multicollision = Multicollision::new(f, f.initial_state(), n)?;
+
+    loop {
+        let mut seen = HashMap::new();
+        for message in multicollision.messages() {
+            let hash = g.hash(&message)?;
+            if let Some(other) = seen.insert(hash.to_vec(), message.clone()) {
+                return Ok(CascadeCollision {
+                    messages: [other, message],
+                    f_calls: f.calls() - f_start,
+                    g_calls: g.calls() - g_start,
+                });
+            }
+        }
+
+        multicollision.extend(f)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::hash::DaviesMeyer;
+
+    #[test]
+    fn multicollision_messages_all_collide() -> Result<()> {
+        let md = MerkleDamgard::new(DaviesMeyer, [0u8; 2], true)?;
+        let multicollision = Multicollision::new(&md, md.initial_state(), 4)?;
+
+        let messages = multicollision.messages().collect_vec();
+        assert_eq!(16, messages.len());
+        assert_eq!(16, messages.iter().map(|m| m.to_vec()).unique().count());
+
+        let hash = md.hash(&messages[0])?;
+        assert!(messages.iter().all(|m| md.hash(m).is_ok_and(|h| h == hash)));
+        assert_eq!(
+            Some(&messages[5]),
+            multicollision.message(&[false, true, false, true]).as_ref()
+        );
+        assert_eq!(None, multicollision.message(&[true; 3]).map(|m| m.to_vec()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn s7c52_iterated_hash_function_multicollisions() -> Result<()> {
+        let f = MerkleDamgard::new(DaviesMeyer, [0u8; 2], true)?;
+        let g = MerkleDamgard::new(DaviesMeyer, [0u8; 4], true)?;
+
+        let res = cascade_collision(&f, &g)?;
+        let [a, b] = &res.messages;
+        assert!(a != b);
+        assert_eq!(f.hash(a)?, f.hash(b)?);
+        assert_eq!(g.hash(a)?, g.hash(b)?);
+
+        // a birthday search on the 48-bit cascade directly would take around 2^24 calls to each
+        assert!(res.f_calls < 1 << 16);
+        assert!(res.g_calls < 1 << 24);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use crate::{
+    Data, Error, Result,
+    attack::merkle_damgard::joux::find_collision_between,
+    hash::{Compress, MerkleDamgard, merkle_damgard::BLOCK_SIZE},
+};
+
+/// A set of messages of every length from `k` to `k + 2^k - 1` blocks, all reaching the same
+/// state. Piece `i` is a collision between a single block and `2^(k - 1 - i) + 1` blocks.
+#[derive(Debug, Clone)]
+pub struct ExpandableMessage {
+    pub pieces: Vec<(Data, Data)>,
+    pub state: Data,
+}
+
+impl ExpandableMessage {
+    pub fn new<C: Compress>(md: &MerkleDamgard<C>, state: &[u8], k: usize) -> Result<Self> {
+        let mut state = Data::from(state);
+        let pieces = (0..k)
+            .rev()
+            .map(|j| {
+                let prefix = Data::from(vec![0u8; BLOCK_SIZE << j]);
+                let long_state = md.iterate(&state, &prefix)?;
+                let ([short, last], next) = find_collision_between(md, [&state, &long_state])?;
+                state = next;
+
+                let long: Box<[u8]> = prefix.iter().chain(last.iter()).copied().collect();
+                Ok((short, Data::from(long)))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { pieces, state })
+    }
+
+    #[must_use]
+    pub fn min_blocks(&self) -> usize {
+        self.pieces.len()
+    }
+
+    #[must_use]
+    pub fn max_blocks(&self) -> usize {
+        self.pieces.len() + (1 << self.pieces.len()) - 1
+    }
+
+    /// The message of exactly `blocks` blocks, if it's in range: the extra blocks over the
+    /// minimum pick which pieces to take long, by their binary expansion.
+    #[must_use]
+    pub fn message(&self, blocks: usize) -> Option<Data> {
+        if blocks < self.min_blocks() || blocks > self.max_blocks() {
+            return None;
+        }
+
+        let extra = blocks - self.min_blocks();
+        let k = self.pieces.len();
+        let bytes: Box<[u8]> = self
+            .pieces
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (short, long))| {
+                let piece = if extra >> (k - 1 - i) & 1 == 1 {
+                    long
+                } else {
+                    short
+                };
+                piece.iter().copied()
+            })
+            .collect();
+        Some(Data::from(bytes))
+    }
+}
+
+/// Finds a different message of the same length and hash as `message`. An expandable message
+/// defeats MD strengthening, so it's enough to find a bridge block from its final state to any
+/// of the target's intermediate states, then pad the expandable message to line up with it.
+pub fn second_preimage<C: Compress>(md: &MerkleDamgard<C>, message: &[u8]) -> Result<Data> {
+    let blocks = message.len() / BLOCK_SIZE;
+    if blocks < 2 {
+        return Err(Error::AttackFailed("the target needs at least two blocks"));
+    }
+    let k = blocks.ilog2() as usize;
+
+    let expandable = ExpandableMessage::new(md, md.initial_state(), k)?;
+
+    // the state after `i` blocks can be bridged to from an expandable message of `i - 1` blocks
+    let mut state = md.initial_state().clone();
+    let mut targets = HashMap::new();
+    for (i, block) in message.chunks_exact(BLOCK_SIZE).enumerate() {
+        state = md.compress(&state, block)?;
+        if (expandable.min_blocks()..=expandable.max_blocks()).contains(&i) {
+            targets.entry(state.to_vec()).or_insert(i + 1);
+        }
+    }
+
+    let mut rng = rand::rng();
+    let (bridge, i) = loop {
+        let block: [u8; BLOCK_SIZE] = rng.random();
+        let next = md.compress(&expandable.state, &block)?;
+        if let Some(&i) = targets.get(&next.to_vec()) {
+            break (block, i);
+        }
+    };
+
+    let prefix = expandable
+        .message(i - 1)
+        .expect("only reachable lengths are targeted");
+    let bytes: Box<[u8]> = prefix
+        .iter()
+        .chain(&bridge)
+        .chain(&message[i * BLOCK_SIZE..])
+        .copied()
+        .collect();
+
+    if *bytes == *message {
+        return Err(Error::AttackFailed("rebuilt the original message"));
+    }
+    Ok(Data::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::hash::DaviesMeyer;
+
+    #[test]
+    fn expandable_message_covers_its_range() -> Result<()> {
+        let md = MerkleDamgard::new(DaviesMeyer, [0u8; 2], true)?;
+        let expandable = ExpandableMessage::new(&md, md.initial_state(), 3)?;
+
+        assert_eq!(None, expandable.message(2).map(|m| m.to_vec()));
+        assert_eq!(None, expandable.message(11).map(|m| m.to_vec()));
+        for blocks in 3..=10 {
+            let message = expandable.message(blocks).unwrap();
+            assert_eq!(blocks * BLOCK_SIZE, message.len());
+            assert_eq!(expandable.state, md.iterate(md.initial_state(), &message)?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn s7c53_kelsey_and_schneiers_expandable_messages() -> Result<()> {
+        let md = MerkleDamgard::new(DaviesMeyer, [0u8; 3], true)?;
+        let mut rng = rand::rng();
+
+        for k in [6, 8, 10] {
+            let mut message = vec![0u8; BLOCK_SIZE << k];
+            rng.fill(message.as_mut_slice());
+
+            let forged = second_preimage(&md, &message)?;
+            assert!(forged != message);
+            assert_eq!(message.len(), forged.len());
+            assert_eq!(md.hash(&message)?, md.hash(&forged)?);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use rayon::prelude::*;
+
+use crate::{Data, Error, Result, blackbox::Oracle};
+
+/// For 128-bit keys, keystream byte 16 leans towards 240 and byte 32 towards 224.
+const BIASES: [(usize, u8); 2] = [(15, 0xf0), (31, 0xe0)];
+
+type Counts = [[u64; 256]; 2];
+
+/// Tallies the ciphertext bytes at each biased position over `samples` encryptions of `request`,
+/// spread across threads with a clone of the oracle each.
+fn tally<O>(oracle: &O, request: &Data, samples: u64) -> Result<Counts>
+where
+    O: Oracle<Output = Data> + Clone + Send + Sync,
+{
+    (0..samples)
+        .into_par_iter()
+        .map_init(|| oracle.clone(), |oracle, _| oracle.query(request))
+        .try_fold(
+            || [[0; 256]; 2],
+            |mut counts: Counts, ciphertext| {
+                let ciphertext = ciphertext?;
+                for (count, &(position, _)) in counts.iter_mut().zip(&BIASES) {
+                    if let Some(&b) = ciphertext.get(position) {
+                        count[b as usize] += 1;
+                    }
+                }
+                Ok(counts)
+            },
+        )
+        .try_reduce(
+            || [[0; 256]; 2],
+            |mut a, b| {
+                for (a, b) in a.iter_mut().flatten().zip(b.iter().flatten()) {
+                    *a += b;
+                }
+                Ok(a)
+            },
+        )
+}
+
+/// Recovers a secret appended to the request from the single-byte keystream biases. Each request
+/// length slides a different secret byte under each biased position; the candidate whose xor with
+/// the biased keystream value turns up most often wins, with votes from both positions combined.
+/// Request lengths that leave every biased position outside the secret are skipped.
+pub fn recover_secret<O>(oracle: &O, samples: u64) -> Result<Data>
+where
+    O: Oracle<Output = Data> + Clone + Send + Sync,
+{
+    let len = oracle.clone().query(&Data::from(vec![]))?.len();
+    let last = BIASES[BIASES.len() - 1].0;
+    if len > last + 1 {
+        return Err(Error::AttackFailed(
+            "the secret runs past the last biased byte",
+        ));
+    }
+
+    let mut scores = vec![[0u64; 256]; len];
+    for prefix in 0..=last {
+        // the index of the secret byte under each biased position, if any
+        let indices = BIASES.map(|(position, _)| position.checked_sub(prefix).filter(|&i| i < len));
+        if indices.iter().all(Option::is_none) {
+            continue;
+        }
+
+        let counts = tally(oracle, &Data::from(vec![b'A'; prefix]), samples)?;
+        for ((count, &(_, bias)), i) in counts.iter().zip(&BIASES).zip(indices) {
+            let Some(i) = i else {
+                continue;
+            };
+            for (c, score) in scores[i].iter_mut().enumerate() {
+                *score += count[c ^ bias as usize];
+            }
+        }
+    }
+
+    let bytes: Box<[u8]> = scores
+        .iter()
+        .map(|score| {
+            (0..=255u8)
+                .max_by_key(|&c| score[c as usize])
+                .expect("256 candidates")
+        })
+        .collect();
+    Ok(Data::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::blackbox::rc4_cookie::Rc4CookieOracle;
+
+    #[test]
+    fn rejects_long_secrets() {
+        let oracle = Rc4CookieOracle::init(Data::from(vec![0; 33]));
+        assert!(recover_secret(&oracle, 1).is_err());
+    }
+
+    #[test]
+    fn recovers_byte_from_both_positions() -> Result<()> {
+        // a single secret byte sits under both biased positions, which keeps this to two tallies
+        let oracle = Rc4CookieOracle::init(Data::from("Z".as_bytes()));
+        let res = recover_secret(&oracle, 1 << 22)?;
+        assert_eq!("Z", res.to_string());
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "slow"]
+    fn s7c56_rc4_single_byte_biases() -> Result<()> {
+        let oracle = Rc4CookieOracle::new()?;
+        let res = recover_secret(&oracle, 1 << 24)?;
+        assert_eq!("BE SURE TO DRINK YOUR OVALTINE", res.to_string());
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+pub mod bleichenbacher;
+pub mod broadcast;
+pub mod common_modulus;
+pub mod franklin_reiter;
+pub mod manger;
+pub mod parity;
+pub mod signature_forgery;
+pub mod unpadded;
+pub mod wiener;
@@ -0,0 +1,211 @@
+use itertools::Itertools;
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{CheckedSub, One};
+
+use crate::{
+    Data, Error, Result,
+    blackbox::Oracle,
+    math::{invmod, random_below},
+    rsa::PublicKey,
+};
+
+struct Attack<'a> {
+    oracle: &'a mut dyn Oracle<Output = bool>,
+    key: &'a PublicKey,
+    queries: usize,
+}
+
+impl Attack<'_> {
+    /// Asks whether `c * s^e` is PKCS#1 conforming.
+    fn conforming(&mut self, c: &BigUint, s: &BigUint) -> Result<bool> {
+        self.queries += 1;
+        let c = (c * s.modpow(&self.key.e, &self.key.n)) % &self.key.n;
+        self.oracle
+            .query(&Data::from_biguint_sized(&c, self.key.size()))
+    }
+
+    /// Finds the smallest `s >= start` that yields a conforming ciphertext.
+    fn search_from(&mut self, c: &BigUint, start: BigUint) -> Result<BigUint> {
+        let mut s = start;
+        while !self.conforming(c, &s)? {
+            s += 1u32;
+        }
+        Ok(s)
+    }
+}
+
+/// Bleichenbacher's 1998 attack: recovers the plaintext block of `data` from an oracle that reveals
+/// whether a ciphertext decrypts to something starting with `00 02`. Returns the block along with
+/// the number of oracle queries spent.
+pub fn bleichenbacher(
+    oracle: &mut dyn Oracle<Output = bool>,
+    key: &PublicKey,
+    data: &Data,
+) -> Result<(Data, usize)> {
+    let n = &key.n;
+    let len = key.size();
+    if len < 11 {
+        return Err(Error::AttackFailed("modulus is too short for PKCS#1 v1.5"));
+    }
+    let b = BigUint::one() << (8 * (len - 2));
+    let b2 = &b * 2u32;
+    let b3 = &b * 3u32;
+
+    let mut attack = Attack {
+        oracle,
+        key,
+        queries: 0,
+    };
+
+    // step 1: blinding, skipped when the ciphertext already conforms
+    let c = data.biguint();
+    let mut rng = rand::rng();
+    let mut s0 = BigUint::one();
+    while !attack.conforming(&c, &s0)? {
+        s0 = random_below(n, &mut rng);
+    }
+    let c0 = (&c * s0.modpow(&key.e, n)) % n;
+
+    // step 2a
+    let mut s = attack.search_from(&c0, n.div_ceil(&b3))?;
+    let mut intervals = vec![(b2.clone(), &b3 - 1u32)];
+
+    loop {
+        intervals = narrow(&intervals, &s, n, &b2, &b3);
+
+        match intervals.as_slice() {
+            [] => return Err(Error::AttackFailed("no intervals remain")),
+            [(lo, hi)] if lo == hi => break,
+            // step 2c
+            [(lo, hi)] => {
+                let mut r = (2u32 * (hi * &s - &b2)).div_ceil(n);
+                s = 'search: loop {
+                    let s_lo = (&b2 + &r * n).div_ceil(hi);
+                    let s_hi = (&b3 - 1u32 + &r * n) / lo;
+
+                    let mut si = s_lo;
+                    while si <= s_hi {
+                        if attack.conforming(&c0, &si)? {
+                            break 'search si;
+                        }
+                        si += 1u32;
+                    }
+
+                    r += 1u32;
+                };
+            }
+            // step 2b
+            _ => s = attack.search_from(&c0, s + 1u32)?,
+        }
+    }
+
+    // step 4
+    let (lo, _) = &intervals[0];
+    let Some(s0_inv) = invmod(&s0, n) else {
+        return Err(Error::AttackFailed("blinding factor is not invertible"));
+    };
+    let m = (lo * s0_inv) % n;
+
+    Ok((Data::from_biguint_sized(&m, len), attack.queries))
+}
+
+/// Step 3: keeps the parts of each interval consistent with `m * s` being conforming, then merges
+/// any that overlap.
+fn narrow(
+    intervals: &[(BigUint, BigUint)],
+    s: &BigUint,
+    n: &BigUint,
+    b2: &BigUint,
+    b3: &BigUint,
+) -> Vec<(BigUint, BigUint)> {
+    let b3_minus_one = b3 - 1u32;
+
+    intervals
+        .iter()
+        .flat_map(|(a, b)| {
+            let r_lo = (a * s)
+                .checked_sub(&b3_minus_one)
+                .map_or(BigUint::ZERO, |x| x.div_ceil(n));
+            let r_hi = (b * s - b2) / n;
+
+            let mut res = vec![];
+            let mut r = r_lo;
+            while r <= r_hi {
+                let lo = (b2 + &r * n).div_ceil(s).max(a.clone());
+                let hi = ((&b3_minus_one + &r * n) / s).min(b.clone());
+                if lo <= hi {
+                    res.push((lo, hi));
+                }
+                r += 1u32;
+            }
+            res
+        })
+        .sorted()
+        .coalesce(|(a1, b1), (a2, b2)| {
+            if a2 <= b1 {
+                Ok((a1, b1.max(b2)))
+            } else {
+                Err(((a1, b1), (a2, b2)))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{blackbox::rsa_pkcs1::RsaPkcs1Oracle, rsa::pkcs1};
+
+    /// Counts the queries that actually reach the oracle.
+    struct Counting<O> {
+        oracle: O,
+        queries: usize,
+    }
+
+    impl<O: Oracle> Oracle for Counting<O> {
+        type Output = O::Output;
+
+        fn query(&mut self, data: &Data) -> crate::Result<O::Output> {
+            self.queries += 1;
+            self.oracle.query(data)
+        }
+    }
+
+    fn test_bleichenbacher(bits: u64) -> Result<()> {
+        let oracle = RsaPkcs1Oracle::new(bits)?;
+        let key = oracle.public_key();
+
+        let c = pkcs1::encrypt(&key, &Data::from("kick it, CC".as_bytes()))?;
+        let mut oracle = Counting { oracle, queries: 0 };
+        assert!(oracle.query(&c)?);
+
+        let (res, queries) = bleichenbacher(&mut oracle, &key, &c)?;
+        assert_eq!("kick it, CC", pkcs1::decode_encryption(&res)?.to_string());
+        assert_eq!(oracle.queries - 1, queries);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_short_moduli() -> Result<()> {
+        let key = PublicKey::new(BigUint::from(3u32), BigUint::from(0xffu32));
+        let mut oracle = RsaPkcs1Oracle::new(512)?;
+        let res = bleichenbacher(&mut oracle, &key, &Data::from(vec![0x01]));
+        assert!(matches!(res, Err(Error::AttackFailed(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn s6c47_bleichenbachers_pkcs1_5_padding_oracle_simple_case() -> Result<()> {
+        test_bleichenbacher(256)
+    }
+
+    #[test]
+    fn s6c48_bleichenbachers_pkcs1_5_padding_oracle_complete_case() -> Result<()> {
+        test_bleichenbacher(768)
+    }
+}
@@ -0,0 +1,118 @@
+use itertools::Itertools;
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+
+use crate::{Data, Error, Result, math::crt, rsa::PublicKey};
+
+/// Håstad's broadcast attack: recovers a message encrypted unpadded under `e` different public
+/// keys that all share the exponent `e`.
+pub fn broadcast(ciphertexts: &[(PublicKey, Data)]) -> Result<Data> {
+    let Some((first, _)) = ciphertexts.first() else {
+        return Err(Error::CiphertextCount {
+            expected: 1,
+            actual: 0,
+        });
+    };
+
+    if !ciphertexts.iter().map(|(key, _)| &key.e).all_equal() {
+        return Err(Error::InvalidKey("public exponents differ"));
+    }
+
+    let e = first
+        .e
+        .to_u32()
+        .ok_or(Error::InvalidKey("public exponent is too large"))?;
+    if ciphertexts.len() != e as usize {
+        return Err(Error::CiphertextCount {
+            expected: e as usize,
+            actual: ciphertexts.len(),
+        });
+    }
+
+    let residues = ciphertexts
+        .iter()
+        .map(|(key, data)| (data.biguint(), key.n.clone()))
+        .collect_vec();
+    let (c, _) = crt(&residues)?;
+
+    let m: BigUint = c.nth_root(e);
+    if m.pow(e) != c {
+        return Err(Error::NotPerfectPower(e));
+    }
+
+    Ok(Data::from_biguint(&m))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{math::prime::gen_prime, rsa::PrivateKey};
+
+    fn broadcast_ciphertexts(e: u32, count: usize, data: &Data) -> Result<Vec<(PublicKey, Data)>> {
+        let e = BigUint::from(e);
+        let res = (0..count)
+            .map(|_| {
+                let key = PrivateKey::generate(256, &e)?.public_key();
+                let c = key.encrypt(data)?;
+                Ok((key, c))
+            })
+            .collect::<crate::Result<_>>()?;
+        Ok(res)
+    }
+
+    #[test]
+    fn s5c40_implement_an_e_3_rsa_broadcast_attack() -> Result<()> {
+        let data = Data::from("hello, world!".as_bytes());
+        let ciphertexts = broadcast_ciphertexts(3, 3, &data)?;
+
+        let res = broadcast(&ciphertexts)?;
+        assert_eq!("hello, world!", res);
+
+        Ok(())
+    }
+
+    #[test]
+    fn broadcast_generalizes_to_larger_e() -> Result<()> {
+        let data = Data::from("hello, world!".as_bytes());
+        let ciphertexts = broadcast_ciphertexts(5, 5, &data)?;
+
+        let res = broadcast(&ciphertexts)?;
+        assert_eq!("hello, world!", res);
+
+        Ok(())
+    }
+
+    #[test]
+    fn broadcast_reports_shared_factor() -> Result<()> {
+        let e = BigUint::from(3u32);
+        let mut rng = rand::rng();
+        let shared = loop {
+            let p = gen_prime(128, &mut rng);
+            if (&p - 1u32) % 3u32 != BigUint::ZERO {
+                break p;
+            }
+        };
+        let mut key_with_shared_factor = || loop {
+            let q = gen_prime(128, &mut rng);
+            if let Ok(key) = PrivateKey::from_primes(e.clone(), shared.clone(), q) {
+                break key.public_key();
+            }
+        };
+
+        let data = Data::from("hello".as_bytes());
+        let mut ciphertexts = broadcast_ciphertexts(3, 1, &data)?;
+        for _ in 0..2 {
+            let key = key_with_shared_factor();
+            let c = key.encrypt(&data)?;
+            ciphertexts.push((key, c));
+        }
+
+        let res = broadcast(&ciphertexts);
+        assert!(matches!(res, Err(Error::NotCoprime(f)) if f == shared));
+
+        Ok(())
+    }
+}
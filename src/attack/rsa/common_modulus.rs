@@ -0,0 +1,60 @@
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::One;
+
+use crate::{Data, Error, Result, math::invmod, rsa::PublicKey};
+
+/// Recovers a message encrypted under two keys that share a modulus but have coprime exponents,
+/// using Bezout coefficients `a * e1 + b * e2 = 1` so that `c1^a * c2^b = m`.
+pub fn common_modulus(
+    (key1, c1): (&PublicKey, &Data),
+    (key2, c2): (&PublicKey, &Data),
+) -> Result<Data> {
+    if key1.n != key2.n {
+        return Err(Error::InvalidKey("moduli differ"));
+    }
+    let n = &key1.n;
+
+    let egcd = BigInt::from(key1.e.clone()).extended_gcd(&BigInt::from(key2.e.clone()));
+    if !egcd.gcd.is_one() {
+        return Err(Error::NotCoprime(egcd.gcd.magnitude().clone()));
+    }
+
+    // a negative exponent means raising the inverse instead
+    let pow = |c: &Data, exp: &BigInt| -> Result<BigUint> {
+        let c = c.biguint();
+        let base = match exp.sign() {
+            Sign::Minus => invmod(&c, n).ok_or_else(|| Error::NotCoprime(c.gcd(n)))?,
+            _ => c,
+        };
+        Ok(base.modpow(exp.magnitude(), n))
+    };
+
+    let m = (pow(c1, &egcd.x)? * pow(c2, &egcd.y)?) % n;
+    Ok(Data::from_biguint(&m))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::rsa::PrivateKey;
+
+    #[test]
+    fn common_modulus_recovers_message() -> Result<()> {
+        let key = PrivateKey::generate(512, &BigUint::from(65537u32))?;
+        let key1 = key.public_key();
+        let key2 = PublicKey::new(BigUint::from(3u32), key.n.clone());
+
+        let data = Data::from("hello, world!".as_bytes());
+        let c1 = key1.encrypt(&data)?;
+        let c2 = key2.encrypt(&data)?;
+
+        let res = common_modulus((&key1, &c1), (&key2, &c2))?;
+        assert_eq!(data, res);
+
+        Ok(())
+    }
+}
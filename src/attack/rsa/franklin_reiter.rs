@@ -0,0 +1,64 @@
+use num_bigint::BigUint;
+use num_traits::{One, ToPrimitive};
+
+use crate::{Data, Error, Result, math::polynomial::Polynomial, rsa::PublicKey};
+
+/// The Franklin–Reiter related-message attack: given encryptions of `m1` and `m2 = a * m1 + b`,
+/// `x - m1` divides both `x^e - c1` and `(a * x + b)^e - c2`, so their GCD over `Z_n` gives `m1`
+/// away. Practical for small `e` such as 3.
+pub fn franklin_reiter(
+    key: &PublicKey,
+    c1: &Data,
+    c2: &Data,
+    a: &BigUint,
+    b: &BigUint,
+) -> Result<Data> {
+    let n = &key.n;
+    let e = key
+        .e
+        .to_u32()
+        .ok_or(Error::InvalidKey("public exponent is too large"))?;
+    let constant = |c: BigUint| Polynomial::new(vec![c], n.clone());
+
+    let x = Polynomial::new(vec![BigUint::ZERO, BigUint::one()], n.clone());
+    let f1 = x.pow(e).sub(&constant(c1.biguint()));
+
+    let related = Polynomial::new(vec![b.clone(), a.clone()], n.clone());
+    let f2 = related.pow(e).sub(&constant(c2.biguint()));
+
+    let gcd = f1.gcd(&f2)?;
+    let [m, _] = gcd.coefficients() else {
+        return Err(Error::AttackFailed("messages share no linear factor"));
+    };
+
+    // the GCD is monic, `x - m1`
+    let m = (n - m) % n;
+    Ok(Data::from_biguint(&m))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::rsa::PrivateKey;
+
+    #[test]
+    fn franklin_reiter_recovers_related_messages() -> Result<()> {
+        let key = PrivateKey::generate(512, &BigUint::from(3u32))?.public_key();
+
+        let m1 = Data::from("attack at dawn, or maybe a little later".as_bytes());
+        let a = BigUint::from(2u32);
+        let b = BigUint::from(12345u32);
+        let m2 = Data::from_biguint(&((&a * m1.biguint() + &b) % &key.n));
+
+        let c1 = key.encrypt(&m1)?;
+        let c2 = key.encrypt(&m2)?;
+
+        let res = franklin_reiter(&key, &c1, &c2, &a, &b)?;
+        assert_eq!(m1, res);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::One;
+
+use crate::{
+    Data, Error, Result,
+    blackbox::{Oracle, rsa_oaep::Decryption},
+    rsa::PublicKey,
+};
+
+struct Attack<'a> {
+    oracle: &'a mut dyn Oracle<Output = Decryption>,
+    key: &'a PublicKey,
+    c: BigUint,
+    queries: usize,
+}
+
+impl Attack<'_> {
+    /// Asks whether `f * m < B`.
+    fn below(&mut self, f: &BigUint) -> Result<bool> {
+        self.queries += 1;
+        let c = (&self.c * f.modpow(&self.key.e, &self.key.n)) % &self.key.n;
+        let res = self
+            .oracle
+            .query(&Data::from_biguint_sized(&c, self.key.size()))?;
+        Ok(res != Decryption::LeadingByte)
+    }
+}
+
+/// Manger's attack: recovers the OAEP-encoded plaintext block of `data` from an oracle that reports
+/// a nonzero leading byte differently from the other decoding failures. Returns the block along
+/// with the number of oracle queries spent.
+pub fn manger(
+    oracle: &mut dyn Oracle<Output = Decryption>,
+    key: &PublicKey,
+    data: &Data,
+) -> Result<(Data, usize)> {
+    let n = &key.n;
+    let len = key.size();
+    let b = BigUint::one() << (8 * (len - 1));
+    if n < &(&b * 2u32) {
+        return Err(Error::AttackFailed("modulus is smaller than 2B"));
+    }
+
+    let mut attack = Attack {
+        oracle,
+        key,
+        c: data.biguint(),
+        queries: 0,
+    };
+
+    // step 1: double until `f1 * m` crosses B, so that `f1 / 2 * m` is in [B / 2, B)
+    let mut f1 = BigUint::from(2u32);
+    while attack.below(&f1)? {
+        f1 <<= 1;
+    }
+    let half_f1 = &f1 >> 1;
+
+    // step 2: step `f2 * m` through [n / 2, n + B) until it wraps to below B
+    let mut f2 = ((n + &b) / &b) * &half_f1;
+    while !attack.below(&f2)? {
+        f2 += &half_f1;
+    }
+
+    // step 3: binary search with `f3 * m` straddling a boundary of `i * n + B`
+    let mut m_min = n.div_ceil(&f2);
+    let mut m_max = (n + &b) / &f2;
+    while m_min < m_max {
+        let f_tmp = (&b * 2u32) / (&m_max - &m_min);
+        let i = (&f_tmp * &m_min) / n;
+        let f3 = (&i * n).div_ceil(&m_min);
+
+        let boundary = &i * n + &b;
+        if attack.below(&f3)? {
+            m_max = boundary / &f3;
+        } else {
+            m_min = boundary.div_ceil(&f3);
+        }
+    }
+
+    Ok((Data::from_biguint_sized(&m_min, len), attack.queries))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+    use sha2::Sha256;
+
+    use super::*;
+    use crate::{blackbox::rsa_oaep::RsaOaepOracle, rsa::oaep};
+
+    #[test]
+    fn oracle_tells_leading_byte_from_padding() -> Result<()> {
+        let label = Data::from("label".as_bytes());
+        let mut oracle = RsaOaepOracle::new(1024, label.clone())?;
+        let key = oracle.public_key();
+        let message = Data::from("kick it, CC".as_bytes());
+
+        let c = oaep::encrypt::<Sha256>(&key, &message, &label)?;
+        assert_eq!(Decryption::Plaintext(message.clone()), oracle.query(&c)?);
+
+        let c = oaep::encrypt::<Sha256>(&key, &message, &Data::from(vec![]))?;
+        assert_eq!(Decryption::Padding, oracle.query(&c)?);
+
+        let c = key.encrypt_int(&(&key.n - 1u32))?;
+        let c = Data::from_biguint_sized(&c, key.size());
+        assert_eq!(Decryption::LeadingByte, oracle.query(&c)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn manger_breaks_sloppy_oaep() -> Result<()> {
+        let label = Data::from(vec![]);
+        let mut oracle = RsaOaepOracle::new(1024, label.clone())?;
+        let key = oracle.public_key();
+
+        let c = oaep::encrypt::<Sha256>(&key, &Data::from("kick it, CC".as_bytes()), &label)?;
+        let (res, queries) = manger(&mut oracle, &key, &c)?;
+
+        assert_eq!(
+            "kick it, CC",
+            oaep::decode::<Sha256>(&res, &label)?.to_string()
+        );
+        assert!(queries < 2 * 1024);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use num_bigint::BigUint;
+use num_rational::Ratio;
+
+use crate::{Data, Result, blackbox::Oracle, rsa::PublicKey};
+
+/// Recovers the plaintext of `data` from a parity oracle. Doubling the plaintext and asking whether
+/// it wrapped around the modulus halves the interval it can lie in, so each query reveals one bit.
+/// `progress` is called with the upper bound after every query.
+pub fn parity_attack(
+    oracle: &mut dyn Oracle<Output = bool>,
+    key: &PublicKey,
+    data: &Data,
+    mut progress: impl FnMut(&Data),
+) -> Result<Data> {
+    let double = key.encrypt_int(&BigUint::from(2u32))?;
+    let mut c = data.biguint();
+    let mut lo = Ratio::from_integer(BigUint::ZERO);
+    let mut hi = Ratio::from_integer(key.n.clone());
+
+    for _ in 0..key.n.bits() {
+        c = (c * &double) % &key.n;
+        let mid = (&lo + &hi) / BigUint::from(2u32);
+
+        if oracle.query(&Data::from_biguint(&c))? {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+
+        progress(&Data::from_biguint(&hi.to_integer()));
+    }
+
+    Ok(Data::from_biguint(&hi.to_integer()))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::blackbox::rsa_parity::RsaParityOracle;
+
+    #[test]
+    fn s6c46_rsa_parity_oracle() -> Result<()> {
+        let mut oracle = RsaParityOracle::new(1024)?;
+        let key = oracle.public_key();
+
+        let data = Data::from_base64(
+            "VGhhdCdzIHdoeSBJIGZvdW5kIHlvdSBkb24ndCBwbGF5IGFyb3VuZCB3aXRoIHRoZSBGdW5reSBDb2xkIE1lZGluYQ==",
+        )?;
+        let c = key.encrypt(&data)?;
+
+        let mut bounds = vec![];
+        let res = parity_attack(&mut oracle, &key, &c, |hi| bounds.push(hi.biguint()))?;
+
+        assert_eq!(
+            "That's why I found you don't play around with the Funky Cold Medina",
+            res.to_string()
+        );
+        assert_eq!(1024, bounds.len());
+        assert!(bounds.is_sorted_by(|a, b| a >= b));
+        assert_eq!(Some(&data.biguint()), bounds.last());
+
+        Ok(())
+    }
+}
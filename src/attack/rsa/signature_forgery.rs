@@ -0,0 +1,80 @@
+use num_bigint::BigUint;
+use num_traits::{One, ToPrimitive};
+
+use crate::{
+    Data, Error, Result,
+    rsa::{PublicKey, pkcs1::HashAlgorithm},
+};
+
+/// Bleichenbacher's e=3 signature forgery against verifiers that don't check for bytes after the
+/// digest: places `00 01 FF 00 || DigestInfo` at the top of the block and takes the rounded-up cube
+/// root, letting the low bytes absorb the error. Fails if the digest leaves too few low bytes for
+/// that, e.g. SHA-256 under a 1024-bit modulus.
+pub fn forge_signature(key: &PublicKey, hash: HashAlgorithm, message: &Data) -> Result<Data> {
+    let len = key.size();
+    let e = key
+        .e
+        .to_u32()
+        .ok_or(Error::InvalidKey("public exponent is too large"))?;
+
+    let digest_info = hash.digest_info(message);
+    let prefix: Box<[u8]> = [0x00, 0x01, 0xff, 0x00]
+        .into_iter()
+        .chain(digest_info.iter().copied())
+        .collect();
+    let Some(garbage) = len.checked_sub(prefix.len()) else {
+        return Err(Error::AttackFailed("modulus is too short for the digest"));
+    };
+
+    let target = BigUint::from_bytes_be(&prefix) << (garbage * 8);
+    let mut s = target.nth_root(e);
+    if s.pow(e) < target {
+        s += BigUint::one();
+    }
+
+    let forged = Data::from_biguint_sized(&s.pow(e), len);
+    if !forged.starts_with(&prefix) {
+        return Err(Error::AttackFailed("no cube root leaves the prefix intact"));
+    }
+
+    Ok(Data::from_biguint_sized(&s, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use super::*;
+    use crate::rsa::{
+        PrivateKey,
+        pkcs1::{verify, verify_sloppy},
+    };
+
+    #[test]
+    fn s6c42_bleichenbachers_e_3_rsa_attack() -> Result<()> {
+        let key = PrivateKey::generate(1024, &BigUint::from(3u32))?.public_key();
+        let message = Data::from("hi mom".as_bytes());
+
+        let signature = forge_signature(&key, HashAlgorithm::Sha1, &message)?;
+        assert!(verify_sloppy(&key, &message, &signature));
+        assert!(!verify(&key, HashAlgorithm::Sha1, &message, &signature));
+
+        let res = forge_signature(&key, HashAlgorithm::Sha256, &message);
+        assert!(matches!(res, Err(Error::AttackFailed(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "slow"]
+    fn s6c42_bleichenbachers_e_3_rsa_attack_sha256() -> Result<()> {
+        let key = PrivateKey::generate(2048, &BigUint::from(3u32))?.public_key();
+        let message = Data::from("hi mom".as_bytes());
+
+        let signature = forge_signature(&key, HashAlgorithm::Sha256, &message)?;
+        assert!(verify_sloppy(&key, &message, &signature));
+        assert!(!verify(&key, HashAlgorithm::Sha256, &message, &signature));
+
+        Ok(())
+    }
+}
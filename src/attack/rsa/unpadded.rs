@@ -0,0 +1,70 @@
+use num_bigint::BigUint;
+use num_traits::One;
+
+use crate::{
+    Data, Result,
+    blackbox::Oracle,
+    math::{invmod, random_below},
+    rsa::PublicKey,
+};
+
+/// Recovers the plaintext of `data` from a decryption oracle that refuses repeated ciphertexts by
+/// blinding it as `S^e * C mod N`.
+pub fn unpadded_message_recovery(
+    oracle: &mut dyn Oracle<Output = Data>,
+    key: &PublicKey,
+    data: &Data,
+) -> Result<Data> {
+    let mut rng = rand::rng();
+    let (s, s_inv) = loop {
+        let s = random_below(&key.n, &mut rng);
+        if s <= BigUint::one() {
+            continue;
+        }
+        if let Some(s_inv) = invmod(&s, &key.n) {
+            break (s, s_inv);
+        }
+    };
+
+    let c = (key.encrypt_int(&s)? * data.biguint()) % &key.n;
+    let p = oracle.query(&Data::from_biguint(&c))?.biguint();
+    let m = (p * s_inv) % &key.n;
+
+    Ok(Data::from_biguint(&m))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{Error, blackbox::rsa_decrypt::RsaDecryptOracle};
+
+    #[test]
+    fn s6c41_implement_unpadded_message_recovery_oracle() -> Result<()> {
+        let mut oracle = RsaDecryptOracle::new(512)?;
+        let key = oracle.public_key();
+
+        let data = Data::from(r#"{"time": 1356304276, "social": "555-55-5555"}"#.as_bytes());
+        let c = key.encrypt(&data)?;
+        assert_eq!(data, oracle.query(&c)?);
+        assert!(matches!(oracle.query(&c), Err(Error::Refused)));
+
+        let res = unpadded_message_recovery(&mut oracle, &key, &c)?;
+        assert_eq!(data, res);
+
+        Ok(())
+    }
+
+    #[test]
+    fn failed_decryptions_are_not_blacklisted() -> Result<()> {
+        let mut oracle = RsaDecryptOracle::new(512)?;
+        let c = Data::from_biguint(&oracle.public_key().n);
+
+        assert!(matches!(oracle.query(&c), Err(Error::MessageOutOfRange)));
+        assert!(matches!(oracle.query(&c), Err(Error::MessageOutOfRange)));
+
+        Ok(())
+    }
+}
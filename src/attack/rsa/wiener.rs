@@ -0,0 +1,120 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{CheckedSub, One, Zero};
+use rand::Rng;
+
+use crate::{
+    Error, Result,
+    math::{continued_fraction::convergents, invmod, prime::gen_prime, random_below},
+    rsa::{PrivateKey, PublicKey},
+};
+
+/// Wiener's attack: when `d < N^(1/4) / 3`, `k / d` shows up among the convergents of `e / N`.
+/// Each candidate is confirmed by factoring `N` from the `phi` it implies.
+pub fn wiener(key: &PublicKey) -> Result<PrivateKey> {
+    let PublicKey { e, n } = key;
+    let mut tried = 0;
+    let mut bound = BigUint::zero();
+
+    for (k, d) in convergents(e.clone(), n.clone()) {
+        tried += 1;
+        bound.clone_from(&d);
+
+        if k.is_zero() {
+            continue;
+        }
+
+        let (phi, rem) = (e * &d - 1u32).div_rem(&k);
+        if !rem.is_zero() || &phi >= n {
+            continue;
+        }
+
+        // p and q are the roots of x^2 - (n - phi + 1)x + n
+        let sum = n - &phi + 1u32;
+        let Some(discriminant) = (&sum * &sum).checked_sub(&(n * 4u32)) else {
+            continue;
+        };
+        let root = discriminant.sqrt();
+        if &root * &root != discriminant || (&sum + &root).is_odd() {
+            continue;
+        }
+
+        let p = (&sum + &root) >> 1;
+        let q = (&sum - &root) >> 1;
+        if &p * &q == *n {
+            return PrivateKey::from_primes(e.clone(), p, q);
+        }
+    }
+
+    Err(Error::NoConvergent { tried, bound })
+}
+
+/// Generates a key whose private exponent is small enough for [`wiener`] to recover. Below 32
+/// bits, `N^(1/4) / 3` leaves too little room for such an exponent.
+pub fn vulnerable_key<R: Rng + ?Sized>(bits: u64, rng: &mut R) -> Result<PrivateKey> {
+    if bits < 32 {
+        return Err(Error::InvalidKey("modulus must be at least 32 bits"));
+    }
+
+    loop {
+        let p = gen_prime(bits - bits / 2, rng);
+        let q = gen_prime(bits / 2, rng);
+        if p == q {
+            continue;
+        }
+
+        let n = &p * &q;
+        let phi = (&p - 1u32) * (&q - 1u32);
+        let limit = n.nth_root(4) / 3u32;
+
+        let d = random_below(&limit, rng);
+        if d <= BigUint::one() {
+            continue;
+        }
+        let Some(e) = invmod(&d, &phi) else {
+            continue;
+        };
+
+        return PrivateKey::from_primes(e, p, q);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn wiener_recovers_small_d() -> Result<()> {
+        let key = vulnerable_key(1024, &mut StdRng::seed_from_u64(36))?;
+        let res = wiener(&key.public_key())?;
+        assert_eq!(key.d, res.d);
+
+        Ok(())
+    }
+
+    #[test]
+    fn vulnerable_key_rejects_small_moduli() -> Result<()> {
+        let mut rng = StdRng::seed_from_u64(36);
+        for bits in [0, 3, 12, 31] {
+            let res = vulnerable_key(bits, &mut rng);
+            assert!(matches!(res, Err(Error::InvalidKey(_))));
+        }
+
+        let key = vulnerable_key(32, &mut rng)?;
+        assert_eq!(key.d, wiener(&key.public_key())?.d);
+        Ok(())
+    }
+
+    #[test]
+    fn wiener_reports_tried_convergents() -> Result<()> {
+        let key = PrivateKey::generate(512, &BigUint::from(65537u32))?;
+        let res = wiener(&key.public_key());
+        assert!(matches!(res, Err(Error::NoConvergent { tried, .. }) if tried > 0));
+
+        Ok(())
+    }
+}
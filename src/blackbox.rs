@@ -2,7 +2,23 @@ use crate::{Data, Result};
 
 pub mod aes_ecb_cbc;
 pub mod aes_ecb_prefix;
+pub mod cbc_mac_bank;
+pub mod compression;
+pub mod dh_mac;
+pub mod rc4_cookie;
+pub mod rsa_decrypt;
+pub mod rsa_oaep;
+pub mod rsa_parity;
+pub mod rsa_pkcs1;
 
 pub trait Blackbox {
     fn process(&mut self, data: &Data) -> Result<Data>;
 }
+
+/// A blackbox that answers queries about its input, and may refuse to answer with
+/// [`Error::Refused`](crate::Error::Refused).
+pub trait Oracle {
+    type Output;
+
+    fn query(&mut self, data: &Data) -> Result<Self::Output>;
+}
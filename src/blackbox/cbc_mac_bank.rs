@@ -0,0 +1,306 @@
+use std::str::FromStr;
+
+use aes::{Aes128, cipher::KeyInit};
+use itertools::Itertools;
+
+use crate::{
+    Data, Error, Result,
+    cipher::CbcMac,
+    error::{InvalidLengthType, ParseError},
+};
+
+/// `from=#{from}&to=#{to}&amount=#{amount}`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    pub from: u32,
+    pub to: u32,
+    pub amount: u64,
+}
+
+/// One `#{to}:#{amount}` entry of a [`Batch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transaction {
+    pub to: u32,
+    pub amount: u64,
+}
+
+/// `from=#{from}&tx_list=#{transactions}`, with transactions separated by `;`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Batch {
+    pub from: u32,
+    pub transactions: Vec<Transaction>,
+}
+
+impl Transfer {
+    #[must_use]
+    pub fn encode(&self) -> Data {
+        let Self { from, to, amount } = self;
+        Data::from(format!("from={from}&to={to}&amount={amount}").into_bytes())
+    }
+
+    pub fn parse(message: &[u8]) -> Result<Self> {
+        let fields = fields(message)?;
+        if fields.len() != 3 {
+            return Err(Error::InvalidEncoding);
+        }
+
+        Ok(Self {
+            from: number(field(&fields, "from")?)?,
+            to: number(field(&fields, "to")?)?,
+            amount: number(field(&fields, "amount")?)?,
+        })
+    }
+}
+
+impl Transaction {
+    #[must_use]
+    pub fn new(to: u32, amount: u64) -> Self {
+        Self { to, amount }
+    }
+
+    pub fn parse(entry: &[u8]) -> Result<Self> {
+        let (to, amount) = split_once(entry, b':').ok_or(Error::InvalidEncoding)?;
+        Ok(Self::new(number(to)?, number(amount)?))
+    }
+}
+
+impl Batch {
+    #[must_use]
+    pub fn encode(&self) -> Data {
+        let transactions = self
+            .transactions
+            .iter()
+            .map(|Transaction { to, amount }| format!("{to}:{amount}"))
+            .join(";");
+        Data::from(format!("from={}&tx_list={transactions}", self.from).into_bytes())
+    }
+
+    /// Parses a batch, skipping over any transaction entries that are malformed rather than
+    /// rejecting the whole batch.
+    pub fn parse(message: &[u8]) -> Result<Self> {
+        let fields = fields(message)?;
+        if fields.len() != 2 {
+            return Err(Error::InvalidEncoding);
+        }
+
+        let transactions = field(&fields, "tx_list")?
+            .split(|&b| b == b';')
+            .filter_map(|entry| Transaction::parse(entry).ok())
+            .collect();
+
+        Ok(Self {
+            from: number(field(&fields, "from")?)?,
+            transactions,
+        })
+    }
+}
+
+fn split_once(input: &[u8], separator: u8) -> Option<(&[u8], &[u8])> {
+    let i = input.iter().position(|&b| b == separator)?;
+    Some((&input[..i], &input[i + 1..]))
+}
+
+fn fields(message: &[u8]) -> Result<Vec<(&[u8], &[u8])>> {
+    message
+        .split(|&b| b == b'&')
+        .map(|pair| split_once(pair, b'=').ok_or(Error::InvalidEncoding))
+        .collect()
+}
+
+fn field<'a>(fields: &[(&[u8], &'a [u8])], name: &'static str) -> Result<&'a [u8]> {
+    let mut values = fields
+        .iter()
+        .filter(|(key, _)| *key == name.as_bytes())
+        .map(|(_, value)| *value);
+
+    match (values.next(), values.next()) {
+        (Some(value), None) => Ok(value),
+        (None, _) => Err(ParseError::MissingField(name).into()),
+        _ => Err(Error::InvalidEncoding),
+    }
+}
+
+fn number<T: FromStr>(value: &[u8]) -> Result<T> {
+    if value.is_empty() || !value.iter().all(u8::is_ascii_digit) {
+        return Err(Error::InvalidEncoding);
+    }
+
+    str::from_utf8(value)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or(Error::InvalidEncoding)
+}
+
+/// Splits a request into its message and the trailing block.
+fn split_block(request: &Data) -> Result<(Data, Data)> {
+    let len = request.len();
+    if len < 16 {
+        return Err(Error::InvalidLength {
+            kind: InvalidLengthType::Message,
+            expected: 16,
+            actual: len,
+        });
+    }
+
+    Ok((
+        Data::from(&request[..len - 16]),
+        Data::from(&request[len - 16..]),
+    ))
+}
+
+/// The server side of the Challenge 49 API, which shares its MAC key with every client.
+pub struct Bank {
+    mac: CbcMac,
+}
+
+/// A client that will only sign requests spending from its own account.
+pub struct Client {
+    mac: CbcMac,
+    account: u32,
+}
+
+impl Bank {
+    #[must_use]
+    pub fn new() -> Self {
+        let key: [u8; 16] = rand::random();
+        let mac = CbcMac::init(Aes128::new(&key.into()));
+        Self { mac }
+    }
+
+    #[must_use]
+    pub fn client(&self, account: u32) -> Client {
+        Client {
+            mac: self.mac.clone(),
+            account,
+        }
+    }
+
+    /// Verifies and parses a `message || IV || MAC` transfer request.
+    pub fn transfer(&self, request: &Data) -> Result<Transfer> {
+        let (rest, mac) = split_block(request)?;
+        let (message, iv) = split_block(&rest)?;
+        self.mac.verify_with_iv(&message, &iv, &mac)?;
+        Transfer::parse(&message)
+    }
+
+    /// Verifies and parses a `message || MAC` batch request, MACed under a zero IV.
+    pub fn batch(&self, request: &Data) -> Result<Batch> {
+        let (message, mac) = split_block(request)?;
+        self.mac.verify(&message, &mac)?;
+        Batch::parse(&message)
+    }
+}
+
+impl Default for Bank {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Client {
+    #[must_use]
+    pub fn account(&self) -> u32 {
+        self.account
+    }
+
+    pub fn transfer(&self, to: u32, amount: u64) -> Result<Data> {
+        let message = Transfer {
+            from: self.account,
+            to,
+            amount,
+        }
+        .encode();
+        let iv: [u8; 16] = rand::random();
+        let mac = self.mac.mac_with_iv(&message, iv)?;
+
+        let bytes: Box<[u8]> = message
+            .iter()
+            .chain(&iv)
+            .chain(mac.iter())
+            .copied()
+            .collect();
+        Ok(Data::from(bytes))
+    }
+
+    pub fn batch(&self, transactions: Vec<Transaction>) -> Result<Data> {
+        let message = Batch {
+            from: self.account,
+            transactions,
+        }
+        .encode();
+        let mac = self.mac.mac(&message)?;
+
+        let bytes: Box<[u8]> = message.iter().chain(mac.iter()).copied().collect();
+        Ok(Data::from(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn parses_what_it_encodes() -> Result<()> {
+        let transfer = Transfer {
+            from: 1,
+            to: 2,
+            amount: 300,
+        };
+        assert_eq!(transfer, Transfer::parse(&transfer.encode())?);
+
+        let batch = Batch {
+            from: 1,
+            transactions: vec![Transaction::new(2, 10), Transaction::new(3, 20)],
+        };
+        assert_eq!("from=1&tx_list=2:10;3:20", batch.encode().to_string());
+        assert_eq!(batch, Batch::parse(&batch.encode())?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_malformed_messages() {
+        assert!(Transfer::parse(b"from=1&to=2").is_err());
+        assert!(Transfer::parse(b"from=1&to=2&amount=3&amount=4").is_err());
+        assert!(Transfer::parse(b"from=1&to=2&amount=-3").is_err());
+        assert!(Batch::parse(b"from=1&tx_list=2:10&x").is_err());
+    }
+
+    #[test]
+    fn skips_malformed_transactions() -> Result<()> {
+        let batch = Batch::parse(b"from=1&tx_list=2:10;\xff:3;4:40")?;
+        assert_eq!(
+            vec![Transaction::new(2, 10), Transaction::new(4, 40)],
+            batch.transactions
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn verifies_client_requests() -> Result<()> {
+        let bank = Bank::new();
+        let client = bank.client(7);
+
+        let request = client.transfer(8, 100)?;
+        assert_eq!(
+            Transfer {
+                from: 7,
+                to: 8,
+                amount: 100
+            },
+            bank.transfer(&request)?
+        );
+
+        let mut tampered = request.to_vec();
+        tampered[5] = b'9';
+        assert!(bank.transfer(&Data::from(tampered)).is_err());
+
+        let request = client.batch(vec![Transaction::new(8, 100)])?;
+        assert_eq!(7, bank.batch(&request)?.from);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use aes::{Aes128, cipher::KeyInit};
+use miniz_oxide::deflate::compress_to_vec;
+
+use crate::{
+    AesCbc, Data, Result,
+    cipher::{AesCtr, Cipher},
+};
+
+use super::Oracle;
+
+pub(crate) const COOKIE: &str = "TmV2ZXIgcmV2ZWFsIHRoZSBXdS1UYW5nIFNlY3JldCE=";
+const COMPRESSION_LEVEL: u8 = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CtrOrCbc {
+    Ctr,
+    Cbc,
+}
+
+/// Compresses an HTTP request carrying a session cookie and the attacker's body, encrypts it under
+/// a fresh key, and reveals only the length of the ciphertext.
+#[derive(Debug, Clone)]
+pub struct CompressionOracle {
+    mode: CtrOrCbc,
+    cookie: String,
+}
+
+impl CompressionOracle {
+    #[must_use]
+    pub fn new(mode: CtrOrCbc) -> Self {
+        Self::init(mode, COOKIE.to_owned())
+    }
+
+    #[must_use]
+    pub fn init(mode: CtrOrCbc, cookie: String) -> Self {
+        Self { mode, cookie }
+    }
+
+    fn format_request(&self, body: &Data) -> Vec<u8> {
+        let headers = format!(
+            "POST / HTTP/1.1\nHost: hapless.com\nCookie: sessionid={}\nContent-Length: {}\n",
+            self.cookie,
+            body.len()
+        );
+        headers
+            .into_bytes()
+            .into_iter()
+            .chain(body.iter().copied())
+            .collect()
+    }
+}
+
+impl Oracle for CompressionOracle {
+    type Output = usize;
+
+    fn query(&mut self, data: &Data) -> Result<usize> {
+        let compressed = compress_to_vec(&self.format_request(data), COMPRESSION_LEVEL);
+
+        let key: [u8; 16] = rand::random();
+        let key = Aes128::new(&key.into());
+        let mut cipher: Box<dyn Cipher> = match self.mode {
+            CtrOrCbc::Ctr => Box::new(AesCtr::init(key, rand::random())),
+            CtrOrCbc::Cbc => Box::new(AesCbc::init(key, rand::random())),
+        };
+
+        let res = cipher.encode(&Data::from(compressed))?;
+        Ok(res.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use super::*;
+
+    #[test]
+    fn leaks_compressibility() -> Result<()> {
+        let mut oracle = CompressionOracle::new(CtrOrCbc::Ctr);
+        let hit = oracle.query(&Data::from(format!("sessionid={COOKIE}").as_bytes()))?;
+        let miss = oracle.query(&Data::from(
+            format!("sessionid={}", COOKIE.to_lowercase()).as_bytes(),
+        ))?;
+        assert!(hit < miss);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use hmac::{Hmac, Mac};
+use num_bigint::BigUint;
+use sha2::Sha256;
+
+use crate::{
+    Data, Result,
+    dh::{Parameters, PrivateKey, PublicKey},
+};
+
+use super::Oracle;
+
+const MESSAGE: &str = "crazy flamboyant for the rap enjoyment";
+
+/// HMAC-SHA256 keyed with the big-endian bytes of a DH shared secret.
+#[must_use]
+pub fn mac(secret: &BigUint, message: &Data) -> Data {
+    let mut mac = Hmac::<Sha256>::new_from_slice(&secret.to_bytes_be())
+        .expect("HMAC accepts keys of any length");
+    mac.update(message);
+    Data::from(mac.finalize().into_bytes().as_slice())
+}
+
+/// Takes the other party's public value and answers with a message MACed under the shared
+/// secret, never checking which subgroup that value came from.
+pub struct DhMacOracle {
+    key: PrivateKey,
+}
+
+impl DhMacOracle {
+    #[must_use]
+    pub fn new(params: Parameters) -> Self {
+        Self::init(PrivateKey::generate(params))
+    }
+
+    #[must_use]
+    pub fn init(key: PrivateKey) -> Self {
+        Self { key }
+    }
+
+    #[must_use]
+    pub fn public_key(&self) -> PublicKey {
+        self.key.public_key()
+    }
+}
+
+impl Oracle for DhMacOracle {
+    type Output = (Data, Data);
+
+    fn query(&mut self, data: &Data) -> Result<(Data, Data)> {
+        let secret = self.key.shared_secret(&data.biguint());
+        let message = Data::from(MESSAGE.as_bytes());
+        let mac = mac(&secret, &message);
+        Ok((message, mac))
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{Data, Result, cipher::Rc4};
+
+use super::Oracle;
+
+const COOKIE: &str = "QkUgU1VSRSBUTyBEUklOSyBZT1VSIE9WQUxUSU5F";
+
+/// Encrypts `request || cookie` under RC4 with a fresh 128-bit key on every query.
+#[derive(Debug, Clone)]
+pub struct Rc4CookieOracle {
+    cookie: Data,
+}
+
+impl Rc4CookieOracle {
+    pub fn new() -> Result<Self> {
+        Ok(Self::init(Data::from_base64(COOKIE)?))
+    }
+
+    #[must_use]
+    pub fn init(cookie: Data) -> Self {
+        Self { cookie }
+    }
+}
+
+impl Oracle for Rc4CookieOracle {
+    type Output = Data;
+
+    fn query(&mut self, data: &Data) -> Result<Data> {
+        let key: [u8; 16] = rand::random();
+        let mut bytes = [data.as_ref(), self.cookie.as_ref()].concat();
+        Rc4::new(key)?.apply_keystream(&mut bytes);
+        Ok(Data::from(bytes))
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    Data, Error, Result,
+    rsa::{PrivateKey, PublicKey},
+};
+
+use super::Oracle;
+
+/// Decrypts unpadded RSA ciphertexts, but only the first time it sees each one.
+pub struct RsaDecryptOracle {
+    key: PrivateKey,
+    seen: HashSet<[u8; 32]>,
+}
+
+impl RsaDecryptOracle {
+    pub fn new(bits: u64) -> Result<Self> {
+        let key = PrivateKey::generate(bits, &BigUint::from(65537u32))?;
+        Ok(Self::init(key))
+    }
+
+    #[must_use]
+    pub fn init(key: PrivateKey) -> Self {
+        Self {
+            key,
+            seen: HashSet::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn public_key(&self) -> PublicKey {
+        self.key.public_key()
+    }
+}
+
+impl Oracle for RsaDecryptOracle {
+    type Output = Data;
+
+    fn query(&mut self, data: &Data) -> Result<Data> {
+        // hash the integer rather than the bytes so that leading zeroes don't count as a new ciphertext
+        let c = data.biguint();
+        let hash = Sha256::digest(c.to_bytes_be()).into();
+        if self.seen.contains(&hash) {
+            return Err(Error::Refused);
+        }
+
+        // only blacklist ciphertexts that actually decrypted
+        let m = self.key.decrypt_int_crt(&c)?;
+        self.seen.insert(hash);
+        Ok(Data::from_biguint(&m))
+    }
+}
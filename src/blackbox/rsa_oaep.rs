@@ -0,0 +1,59 @@
+use num_bigint::BigUint;
+use sha2::Sha256;
+
+use crate::{
+    Data, Result,
+    rsa::{PrivateKey, PublicKey, oaep},
+};
+
+use super::Oracle;
+
+/// How [`RsaOaepOracle`] answers a ciphertext.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Decryption {
+    Plaintext(Data),
+    /// The encoded message doesn't start with a zero byte.
+    LeadingByte,
+    /// The leading byte is zero, but the label hash or the padding is wrong.
+    Padding,
+}
+
+/// A SHA-256 OAEP decryptor that checks the leading zero byte before anything else and reports
+/// that failure differently, revealing whether the plaintext is below `2^(8(k - 1))`.
+pub struct RsaOaepOracle {
+    key: PrivateKey,
+    label: Data,
+}
+
+impl RsaOaepOracle {
+    pub fn new(bits: u64, label: Data) -> Result<Self> {
+        let key = PrivateKey::generate(bits, &BigUint::from(65537u32))?;
+        Ok(Self::init(key, label))
+    }
+
+    #[must_use]
+    pub fn init(key: PrivateKey, label: Data) -> Self {
+        Self { key, label }
+    }
+
+    #[must_use]
+    pub fn public_key(&self) -> PublicKey {
+        self.key.public_key()
+    }
+}
+
+impl Oracle for RsaOaepOracle {
+    type Output = Decryption;
+
+    fn query(&mut self, data: &Data) -> Result<Decryption> {
+        let len = self.key.public_key().size();
+        let m = self.key.decrypt_int_crt(&data.biguint())?;
+        let em = Data::from_biguint_sized(&m, len);
+        if em.first() != Some(&0x00) {
+            return Ok(Decryption::LeadingByte);
+        }
+
+        Ok(oaep::decode::<Sha256>(&em, &self.label)
+            .map_or(Decryption::Padding, Decryption::Plaintext))
+    }
+}
@@ -0,0 +1,39 @@
+use num_bigint::BigUint;
+
+use crate::{
+    Data, Result,
+    rsa::{PrivateKey, PublicKey},
+};
+
+use super::Oracle;
+
+/// Decrypts RSA ciphertexts and reveals only whether the plaintext is even.
+pub struct RsaParityOracle {
+    key: PrivateKey,
+}
+
+impl RsaParityOracle {
+    pub fn new(bits: u64) -> Result<Self> {
+        let key = PrivateKey::generate(bits, &BigUint::from(65537u32))?;
+        Ok(Self::init(key))
+    }
+
+    #[must_use]
+    pub fn init(key: PrivateKey) -> Self {
+        Self { key }
+    }
+
+    #[must_use]
+    pub fn public_key(&self) -> PublicKey {
+        self.key.public_key()
+    }
+}
+
+impl Oracle for RsaParityOracle {
+    type Output = bool;
+
+    fn query(&mut self, data: &Data) -> Result<bool> {
+        let m = self.key.decrypt_int_crt(&data.biguint())?;
+        Ok(!m.bit(0))
+    }
+}
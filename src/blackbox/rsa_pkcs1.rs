@@ -0,0 +1,41 @@
+use num_bigint::BigUint;
+
+use crate::{
+    Data, Result,
+    rsa::{PrivateKey, PublicKey},
+};
+
+use super::Oracle;
+
+/// Decrypts RSA ciphertexts and reveals only whether the plaintext starts with `00 02`.
+pub struct RsaPkcs1Oracle {
+    key: PrivateKey,
+}
+
+impl RsaPkcs1Oracle {
+    pub fn new(bits: u64) -> Result<Self> {
+        let key = PrivateKey::generate(bits, &BigUint::from(3u32))?;
+        Ok(Self::init(key))
+    }
+
+    #[must_use]
+    pub fn init(key: PrivateKey) -> Self {
+        Self { key }
+    }
+
+    #[must_use]
+    pub fn public_key(&self) -> PublicKey {
+        self.key.public_key()
+    }
+}
+
+impl Oracle for RsaPkcs1Oracle {
+    type Output = bool;
+
+    fn query(&mut self, data: &Data) -> Result<bool> {
+        let len = self.key.public_key().size();
+        let m = self.key.decrypt_int_crt(&data.biguint())?;
+        let em = Data::from_biguint_sized(&m, len);
+        Ok(em.starts_with(&[0x00, 0x02]))
+    }
+}
@@ -1,10 +1,16 @@
 use crate::{Data, Result};
 
 pub mod aes_cbc;
+pub mod aes_ctr;
 pub mod aes_ecb;
+pub mod cbc_mac;
+pub mod rc4;
 
 pub use aes_cbc::AesCbc;
+pub use aes_ctr::AesCtr;
 pub use aes_ecb::AesEcb;
+pub use cbc_mac::CbcMac;
+pub use rc4::Rc4;
 
 pub trait Cipher {
     fn decode(&mut self, data: &Data) -> Result<Data>;
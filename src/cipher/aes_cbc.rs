@@ -67,7 +67,7 @@ impl Cipher for AesCbc {
                 let bytes = GenericArray::from_mut_slice(&mut xor);
                 self.cipher.encrypt_block_mut(bytes);
                 acc.extend(bytes.as_slice());
-                (data, acc)
+                (Data::from(bytes.as_slice()), acc)
             },
         );
         let data = Data::from(bytes);
@@ -93,4 +93,15 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn is_invertible() -> Result<()> {
+        let data = Data::from(include_str!("../../data/funky.txt").as_bytes());
+        let mut cipher = AesCbc::new("YELLOW SUBMARINE", [7u8; 16])?;
+        let res = cipher.decode(&cipher.clone().encode(&data)?)?;
+
+        assert_eq!(data, res);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,84 @@
+use aes::{Aes128, cipher::KeyInit};
+
+use crate::{Data, Error, Result, cipher::aes_ecb::AesEcb, error::InvalidLengthType};
+
+use super::Cipher;
+
+/// AES in CTR mode, with a 64-bit little-endian nonce followed by a 64-bit little-endian block
+/// counter as the keystream input.
+#[derive(Debug, Clone)]
+pub struct AesCtr {
+    cipher: Aes128,
+    nonce: u64,
+}
+
+impl AesCtr {
+    pub fn new(key: impl AsRef<[u8]>, nonce: u64) -> Result<Self> {
+        let key = key.as_ref();
+        let cipher = Aes128::new_from_slice(key).map_err(|_| Error::InvalidLength {
+            kind: InvalidLengthType::Key,
+            expected: 16,
+            actual: key.len(),
+        })?;
+
+        Ok(Self::init(cipher, nonce))
+    }
+
+    #[must_use]
+    pub fn init(cipher: Aes128, nonce: u64) -> Self {
+        Self { cipher, nonce }
+    }
+
+    fn keystream(&self, len: usize) -> Result<Data> {
+        let blocks: Box<[u8]> = (0..len.div_ceil(16) as u64)
+            .flat_map(|counter| {
+                self.nonce
+                    .to_le_bytes()
+                    .into_iter()
+                    .chain(counter.to_le_bytes())
+            })
+            .collect();
+
+        let mut ecb = AesEcb::init(self.cipher.clone(), false);
+        let keystream = ecb.encode(&Data::from(blocks))?;
+        Ok(Data::from(&keystream[..len]))
+    }
+}
+
+impl Cipher for AesCtr {
+    fn decode(&mut self, data: &Data) -> Result<Data> {
+        self.encode(data)
+    }
+
+    fn encode(&mut self, data: &Data) -> Result<Data> {
+        if data.is_empty() {
+            return Ok(data.clone());
+        }
+
+        Ok(data ^ self.keystream(data.len())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn s3c18_implement_ctr_the_stream_cipher_mode() -> Result<()> {
+        let data = Data::from_base64(
+            "L77na/nrFsKvynd6HzOoG7GHTLXsTVu9qvY/2syLXzhPweyyMTJULu/6/kXX0KSvoOLSFQ==",
+        )?;
+        let mut cipher = AesCtr::new("YELLOW SUBMARINE", 0)?;
+        let res = cipher.decode(&data)?;
+
+        assert_eq!(
+            "Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby ",
+            res.to_string()
+        );
+
+        Ok(())
+    }
+}
@@ -30,6 +30,12 @@ impl AesEcb {
     pub fn init(cipher: Aes128, pad: bool) -> Self {
         Self { cipher, pad }
     }
+
+    /// Encrypts a single block in place, skipping the allocations of [`Cipher::encode`].
+    pub fn encrypt_block(&mut self, block: &mut [u8; 16]) {
+        self.cipher
+            .encrypt_block_mut(GenericArray::from_mut_slice(block));
+    }
 }
 
 impl Cipher for AesEcb {
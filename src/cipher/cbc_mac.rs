@@ -0,0 +1,87 @@
+use aes::{Aes128, cipher::KeyInit};
+
+use crate::{Data, Error, Result, error::InvalidLengthType};
+
+use super::{AesCbc, Cipher};
+
+/// CBC-MAC: the last block of the AES-CBC encryption of the padded message.
+#[derive(Debug, Clone)]
+pub struct CbcMac {
+    cipher: Aes128,
+}
+
+impl CbcMac {
+    pub fn new(key: impl AsRef<[u8]>) -> Result<Self> {
+        let key = key.as_ref();
+        let cipher = Aes128::new_from_slice(key).map_err(|_| Error::InvalidLength {
+            kind: InvalidLengthType::Key,
+            expected: 16,
+            actual: key.len(),
+        })?;
+
+        Ok(Self::init(cipher))
+    }
+
+    #[must_use]
+    pub fn init(cipher: Aes128) -> Self {
+        Self { cipher }
+    }
+
+    /// The MAC under a fixed all-zero IV.
+    pub fn mac(&self, message: &Data) -> Result<Data> {
+        self.mac_with_iv(message, [0u8; 16])
+    }
+
+    /// The MAC under a caller-chosen IV, which must travel with the message.
+    pub fn mac_with_iv(&self, message: &Data, iv: impl AsRef<[u8]>) -> Result<Data> {
+        let iv = iv.as_ref();
+        let iv = iv.try_into().map_err(|_| Error::InvalidLength {
+            kind: InvalidLengthType::IV,
+            expected: 16,
+            actual: iv.len(),
+        })?;
+
+        let encoded = AesCbc::init(self.cipher.clone(), iv).encode(message)?;
+        Ok(Data::from(&encoded[encoded.len() - 16..]))
+    }
+
+    pub fn verify(&self, message: &Data, mac: &Data) -> Result<()> {
+        self.verify_with_iv(message, [0u8; 16], mac)
+    }
+
+    pub fn verify_with_iv(&self, message: &Data, iv: impl AsRef<[u8]>, mac: &Data) -> Result<()> {
+        if self.mac_with_iv(message, iv)? == mac {
+            Ok(())
+        } else {
+            Err(Error::InvalidMac)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn s7c50_hashes_javascript_snippet() -> Result<()> {
+        let mac = CbcMac::new("YELLOW SUBMARINE")?;
+        let res = mac.mac(&Data::from("alert('MZA who was that?');\n".as_bytes()))?;
+        assert_eq!("296b8d7cb78a243dda4d0a61d33bbdd1", res.hex());
+        Ok(())
+    }
+
+    #[test]
+    fn depends_on_iv() -> Result<()> {
+        let mac = CbcMac::new("YELLOW SUBMARINE")?;
+        let message = Data::from("from=1&to=2&amount=3".as_bytes());
+        let tag = mac.mac_with_iv(&message, [1u8; 16])?;
+
+        assert!(mac.verify_with_iv(&message, [1u8; 16], &tag).is_ok());
+        assert!(mac.verify_with_iv(&message, [2u8; 16], &tag).is_err());
+
+        Ok(())
+    }
+}
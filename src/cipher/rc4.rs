@@ -0,0 +1,81 @@
+use crate::{Data, Error, Result};
+
+use super::Cipher;
+
+/// RC4, restarting the keystream from the key schedule on every call.
+#[derive(Debug, Clone)]
+pub struct Rc4 {
+    state: [u8; 256],
+}
+
+impl Rc4 {
+    pub fn new(key: impl AsRef<[u8]>) -> Result<Self> {
+        let key = key.as_ref();
+        if key.is_empty() || key.len() > 256 {
+            return Err(Error::InvalidKey("RC4 keys are 1 to 256 bytes"));
+        }
+
+        let mut state = [0u8; 256];
+        for (i, s) in state.iter_mut().enumerate() {
+            *s = i as u8;
+        }
+
+        let mut j = 0u8;
+        for i in 0..256 {
+            j = j.wrapping_add(state[i]).wrapping_add(key[i % key.len()]);
+            state.swap(i, j as usize);
+        }
+
+        Ok(Self { state })
+    }
+
+    /// Xors the keystream into `bytes` in place.
+    pub fn apply_keystream(&self, bytes: &mut [u8]) {
+        let mut state = self.state;
+        let (mut i, mut j) = (0u8, 0u8);
+
+        for b in bytes {
+            i = i.wrapping_add(1);
+            j = j.wrapping_add(state[i as usize]);
+            state.swap(i as usize, j as usize);
+            *b ^= state[state[i as usize].wrapping_add(state[j as usize]) as usize];
+        }
+    }
+}
+
+impl Cipher for Rc4 {
+    fn decode(&mut self, data: &Data) -> Result<Data> {
+        self.encode(data)
+    }
+
+    fn encode(&mut self, data: &Data) -> Result<Data> {
+        let mut bytes = data.to_vec();
+        self.apply_keystream(&mut bytes);
+        Ok(Data::from(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn matches_known_vectors() -> Result<()> {
+        let vectors = [
+            ("Key", "Plaintext", "bbf316e8d940af0ad3"),
+            ("Wiki", "pedia", "1021bf0420"),
+            ("Secret", "Attack at dawn", "45a01f645fc35b383552544b9bf5"),
+        ];
+
+        for (key, plaintext, ciphertext) in vectors {
+            let mut cipher = Rc4::new(key)?;
+            let res = cipher.encode(&Data::from(plaintext.as_bytes()))?;
+            assert_eq!(ciphertext, res.hex());
+        }
+
+        Ok(())
+    }
+}
@@ -6,7 +6,9 @@ use std::{
 use crate::hamming_distance::hamming_distance;
 
 pub mod base64;
+pub mod biguint;
 pub mod hex;
+pub mod pem;
 pub mod xor;
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,44 @@
+use num_bigint::BigUint;
+
+use super::Data;
+
+impl Data {
+    #[must_use]
+    pub fn from_biguint(n: &BigUint) -> Self {
+        Self(n.to_bytes_be().into_boxed_slice())
+    }
+
+    /// Like [`Data::from_biguint`], but left-pads with zeroes to at least `len` bytes.
+    #[must_use]
+    pub fn from_biguint_sized(n: &BigUint, len: usize) -> Self {
+        let bytes = n.to_bytes_be();
+        let padding = len.saturating_sub(bytes.len());
+        let bytes: Box<[u8]> = std::iter::repeat_n(0, padding).chain(bytes).collect();
+        Self(bytes)
+    }
+
+    #[must_use]
+    pub fn biguint(&self) -> BigUint {
+        BigUint::from_bytes_be(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn is_invertible() {
+        let s = "hello, world!";
+        let data = Data::from_biguint(&Data::from(s.as_bytes()).biguint());
+        assert_eq!(s, data);
+    }
+
+    #[test]
+    fn from_biguint_sized_pads_with_zeroes() {
+        let res = Data::from_biguint_sized(&BigUint::from(0x0102u32), 4);
+        assert_eq!(res, [0, 0, 1, 2]);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{Result, error::ParseError};
+
+use super::Data;
+
+const LINE_WIDTH: usize = 64;
+
+impl Data {
+    /// Decodes the first PEM block with the given label, e.g. `RSA PRIVATE KEY`.
+    pub fn from_pem(input: impl AsRef<str>, label: &str) -> Result<Self> {
+        let input = input.as_ref();
+        let begin = format!("-----BEGIN {label}-----");
+        let end = format!("-----END {label}-----");
+
+        let missing = || ParseError::MissingPemBlock(label.to_owned());
+        let (_, rest) = input.split_once(&begin).ok_or_else(missing)?;
+        let (body, _) = rest.split_once(&end).ok_or_else(missing)?;
+
+        let body: String = body.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+        Self::from_base64(body)
+    }
+
+    #[must_use]
+    pub fn pem(&self, label: &str) -> String {
+        let base64 = self.base64();
+        let lines = base64
+            .as_bytes()
+            .chunks(LINE_WIDTH)
+            .map(|line| str::from_utf8(line).expect("base64 is ascii"));
+
+        let mut res = format!("-----BEGIN {label}-----\n");
+        for line in lines {
+            res.push_str(line);
+            res.push('\n');
+        }
+        res.push_str(&format!("-----END {label}-----\n"));
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn is_invertible_with_openssl_output() -> Result<()> {
+        let pem = include_str!("../../data/rsa_pkcs8.pem");
+        let data = Data::from_pem(pem, "PRIVATE KEY")?;
+        assert_eq!(pem, data.pem("PRIVATE KEY"));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_wrong_label() {
+        let pem = include_str!("../../data/rsa_pkcs8.pem");
+        assert!(Data::from_pem(pem, "RSA PRIVATE KEY").is_err());
+    }
+}
@@ -0,0 +1,289 @@
+use num_bigint::BigUint;
+
+use crate::{Data, Error, Result};
+
+pub const RSA_ENCRYPTION: &[u64] = &[1, 2, 840, 113_549, 1, 1, 1];
+pub const DSA: &[u64] = &[1, 2, 840, 10040, 4, 1];
+
+const INTEGER: u8 = 0x02;
+const BIT_STRING: u8 = 0x03;
+const OCTET_STRING: u8 = 0x04;
+const NULL: u8 = 0x05;
+const OBJECT_IDENTIFIER: u8 = 0x06;
+const SEQUENCE: u8 = 0x30;
+
+/// The subset of ASN.1 DER needed for key material. Integers are non-negative only.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Der {
+    Integer(BigUint),
+    BitString { unused: u8, data: Data },
+    OctetString(Data),
+    Null,
+    ObjectIdentifier(Vec<u64>),
+    Sequence(Vec<Der>),
+}
+
+impl Der {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let (der, rest) = Self::parse(data)?;
+        if !rest.is_empty() {
+            return Err(Error::InvalidEncoding);
+        }
+
+        Ok(der)
+    }
+
+    fn parse(data: &[u8]) -> Result<(Self, &[u8])> {
+        let (&tag, rest) = data.split_first().ok_or(Error::InvalidEncoding)?;
+        let (len, rest) = parse_length(rest)?;
+        let (contents, rest) = rest.split_at_checked(len).ok_or(Error::InvalidEncoding)?;
+
+        let der = match tag {
+            INTEGER => {
+                // negative integers and non-minimal encodings are rejected
+                match contents {
+                    [] => return Err(Error::InvalidEncoding),
+                    [first, ..] if first & 0x80 != 0 => return Err(Error::InvalidEncoding),
+                    [0x00, second, ..] if second & 0x80 == 0 => {
+                        return Err(Error::InvalidEncoding);
+                    }
+                    _ => Self::Integer(BigUint::from_bytes_be(contents)),
+                }
+            }
+            BIT_STRING => {
+                let (&unused, data) = contents.split_first().ok_or(Error::InvalidEncoding)?;
+                if unused > 7 {
+                    return Err(Error::InvalidEncoding);
+                }
+                Self::BitString {
+                    unused,
+                    data: Data::from(data),
+                }
+            }
+            OCTET_STRING => Self::OctetString(Data::from(contents)),
+            NULL if contents.is_empty() => Self::Null,
+            OBJECT_IDENTIFIER => Self::ObjectIdentifier(parse_oid(contents)?),
+            SEQUENCE => {
+                let mut items = vec![];
+                let mut contents = contents;
+                while !contents.is_empty() {
+                    let (item, rest) = Self::parse(contents)?;
+                    items.push(item);
+                    contents = rest;
+                }
+                Self::Sequence(items)
+            }
+            _ => return Err(Error::InvalidEncoding),
+        };
+
+        Ok((der, rest))
+    }
+
+    #[must_use]
+    pub fn encode(&self) -> Data {
+        let (tag, contents) = match self {
+            Der::Integer(n) => {
+                let mut bytes = n.to_bytes_be();
+                if bytes[0] & 0x80 != 0 {
+                    bytes.insert(0, 0x00);
+                }
+                (INTEGER, bytes)
+            }
+            Der::BitString { unused, data } => {
+                let bytes = std::iter::once(*unused)
+                    .chain(data.iter().copied())
+                    .collect();
+                (BIT_STRING, bytes)
+            }
+            Der::OctetString(data) => (OCTET_STRING, data.to_vec()),
+            Der::Null => (NULL, vec![]),
+            Der::ObjectIdentifier(arcs) => (OBJECT_IDENTIFIER, encode_oid(arcs)),
+            Der::Sequence(items) => {
+                let bytes = items
+                    .iter()
+                    .flat_map(|item| item.encode().to_vec())
+                    .collect();
+                (SEQUENCE, bytes)
+            }
+        };
+
+        let bytes: Box<[u8]> = std::iter::once(tag)
+            .chain(encode_length(contents.len()))
+            .chain(contents)
+            .collect();
+        Data::from(bytes)
+    }
+
+    pub fn as_integer(&self) -> Result<&BigUint> {
+        match self {
+            Der::Integer(n) => Ok(n),
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+
+    /// The contents of a bit string with no unused bits, as every key format uses.
+    pub fn as_bit_string(&self) -> Result<&Data> {
+        match self {
+            Der::BitString { unused: 0, data } => Ok(data),
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+
+    pub fn as_octet_string(&self) -> Result<&Data> {
+        match self {
+            Der::OctetString(data) => Ok(data),
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+
+    pub fn as_oid(&self) -> Result<&[u64]> {
+        match self {
+            Der::ObjectIdentifier(arcs) => Ok(arcs),
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+
+    pub fn as_sequence(&self) -> Result<&[Der]> {
+        match self {
+            Der::Sequence(items) => Ok(items),
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+}
+
+impl From<BigUint> for Der {
+    fn from(value: BigUint) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<&BigUint> for Der {
+    fn from(value: &BigUint) -> Self {
+        Self::Integer(value.clone())
+    }
+}
+
+fn parse_length(data: &[u8]) -> Result<(usize, &[u8])> {
+    let (&first, rest) = data.split_first().ok_or(Error::InvalidEncoding)?;
+    if first & 0x80 == 0 {
+        return Ok((first as usize, rest));
+    }
+
+    // indefinite lengths aren't allowed in DER
+    let count = (first & 0x7f) as usize;
+    if count == 0 || count > size_of::<usize>() {
+        return Err(Error::InvalidEncoding);
+    }
+
+    let (bytes, rest) = rest.split_at_checked(count).ok_or(Error::InvalidEncoding)?;
+    let len = bytes.iter().fold(0usize, |acc, &b| (acc << 8) | b as usize);
+    if len < 0x80 || bytes[0] == 0 {
+        return Err(Error::InvalidEncoding);
+    }
+
+    Ok((len, rest))
+}
+
+fn encode_length(len: usize) -> Vec<u8> {
+    if len < 0x80 {
+        return vec![len as u8];
+    }
+
+    let bytes = len.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count();
+    let bytes = &bytes[skip..];
+
+    let count = 0x80 | bytes.len() as u8;
+    std::iter::once(count)
+        .chain(bytes.iter().copied())
+        .collect()
+}
+
+fn parse_oid(contents: &[u8]) -> Result<Vec<u64>> {
+    let mut arcs = vec![];
+    let mut value = 0u64;
+
+    for (i, &b) in contents.iter().enumerate() {
+        value = value.checked_mul(0x80).ok_or(Error::InvalidEncoding)? | u64::from(b & 0x7f);
+
+        if b & 0x80 == 0 {
+            if arcs.is_empty() {
+                // the first subidentifier packs the first two arcs
+                let first = (value / 40).min(2);
+                arcs.push(first);
+                arcs.push(value - first * 40);
+            } else {
+                arcs.push(value);
+            }
+            value = 0;
+        } else if i == contents.len() - 1 {
+            return Err(Error::InvalidEncoding);
+        }
+    }
+
+    if arcs.is_empty() {
+        return Err(Error::InvalidEncoding);
+    }
+
+    Ok(arcs)
+}
+
+fn encode_oid(arcs: &[u64]) -> Vec<u8> {
+    let (first, rest) = match arcs {
+        [a, b, rest @ ..] => (a * 40 + b, rest),
+        [a] => (a * 40, &[][..]),
+        [] => return vec![],
+    };
+
+    std::iter::once(first)
+        .chain(rest.iter().copied())
+        .flat_map(|arc| {
+            let groups = (u64::BITS - arc.leading_zeros()).div_ceil(7).max(1);
+            (0..groups).rev().map(move |i| {
+                let b = ((arc >> (7 * i)) & 0x7f) as u8;
+                if i == 0 { b } else { b | 0x80 }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn is_invertible() -> Result<()> {
+        let der = Der::Sequence(vec![
+            Der::Integer(BigUint::from(0u32)),
+            Der::Integer(BigUint::from(0x80u32)),
+            Der::Sequence(vec![
+                Der::ObjectIdentifier(RSA_ENCRYPTION.to_vec()),
+                Der::Null,
+            ]),
+            Der::OctetString(Data::from(vec![0xab; 300])),
+            Der::BitString {
+                unused: 0,
+                data: Data::from(vec![1, 2, 3]),
+            },
+        ]);
+
+        let encoded = der.encode();
+        assert_eq!(der, Der::decode(&encoded)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn encodes_known_values() -> Result<()> {
+        let oid = Der::ObjectIdentifier(RSA_ENCRYPTION.to_vec());
+        assert_eq!("06092a864886f70d010101", oid.encode().hex());
+
+        let int = Der::Integer(BigUint::from(0x80u32));
+        assert_eq!("02020080", int.encode().hex());
+
+        Ok(())
+    }
+}
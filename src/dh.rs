@@ -0,0 +1,174 @@
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use rand::Rng;
+
+use crate::{
+    group::Group,
+    math::{constant, invmod, random_below},
+};
+
+const P: &str = "7199773997391911030609999317773941274322764333428698921736339643928346453700085358802973900485592910475480089726140708102474957429903531369589969318716771";
+const Q: &str = "236234353446506858198510045061214171961";
+const G: &str = "4565356397095740655436854503483826832136106141639563487732438195343690437606117828318042418238184896212352329118608100083187535033402010599512641674644143";
+
+const P_58: &str = "11470374874925275658116663507232161402086650258453896274534991676898999262641581519101074740642369848233294239851519212341844337347119899874391456329785623";
+const Q_58: &str = "335062023296420808191071248367701059461";
+const G_58: &str = "622952335333961296978159266084741085889881358738459939978290179936063635566740258555167783009058567397963466103140082647486611657350811560630587013183357";
+
+/// A prime `p` and a generator `g` of a subgroup of prime order `q`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameters {
+    pub p: BigUint,
+    pub q: BigUint,
+    pub g: BigUint,
+}
+
+impl Parameters {
+    #[must_use]
+    pub fn new(p: BigUint, q: BigUint, g: BigUint) -> Self {
+        Self { p, q, g }
+    }
+
+    /// The cofactor `(p - 1) / q`, whose small factors give away subgroups of small order.
+    #[must_use]
+    pub fn cofactor(&self) -> BigUint {
+        (&self.p - 1u32) / &self.q
+    }
+
+    /// The group from Challenge 58, where the small factors of `p - 1` fall well short of `q`.
+    #[must_use]
+    pub fn challenge_58() -> Self {
+        Self::new(constant(P_58, 10), constant(Q_58, 10), constant(G_58, 10))
+    }
+}
+
+/// The group from Challenge 57, where `p - 1` has plenty of small factors besides `q`.
+impl Default for Parameters {
+    fn default() -> Self {
+        Self::new(constant(P, 10), constant(Q, 10), constant(G, 10))
+    }
+}
+
+/// The subgroup of order `q` in the integers modulo `p`.
+impl Group for Parameters {
+    type Element = BigUint;
+
+    fn identity(&self) -> BigUint {
+        BigUint::one()
+    }
+
+    fn generator(&self) -> BigUint {
+        self.g.clone()
+    }
+
+    fn order(&self) -> &BigUint {
+        &self.q
+    }
+
+    fn combine(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a * b % &self.p
+    }
+
+    fn invert(&self, a: &BigUint) -> Option<BigUint> {
+        invmod(&(a % &self.p), &self.p)
+    }
+
+    fn scale(&self, a: &BigUint, n: &BigUint) -> BigUint {
+        a.modpow(n, &self.p)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    pub params: Parameters,
+    pub y: BigUint,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivateKey {
+    pub params: Parameters,
+    pub x: BigUint,
+    pub y: BigUint,
+}
+
+impl PublicKey {
+    #[must_use]
+    pub fn new(params: Parameters, y: BigUint) -> Self {
+        Self { params, y }
+    }
+}
+
+impl PrivateKey {
+    #[must_use]
+    pub fn generate(params: Parameters) -> Self {
+        Self::generate_with_rng(params, &mut rand::rng())
+    }
+
+    #[must_use]
+    pub fn generate_with_rng<R: Rng + ?Sized>(params: Parameters, rng: &mut R) -> Self {
+        let x = loop {
+            let x = random_below(&params.q, rng);
+            if !x.is_zero() {
+                break x;
+            }
+        };
+
+        Self::from_x(params, x)
+    }
+
+    #[must_use]
+    pub fn from_x(params: Parameters, x: BigUint) -> Self {
+        let y = params.g.modpow(&x, &params.p);
+        Self { params, x, y }
+    }
+
+    #[must_use]
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::new(self.params.clone(), self.y.clone())
+    }
+
+    /// Raises the other party's public value to our secret, without checking that it lies in the
+    /// subgroup generated by `g`.
+    #[must_use]
+    pub fn shared_secret(&self, y: &BigUint) -> BigUint {
+        y.modpow(&self.x, &self.params.p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn default_parameters_are_consistent() {
+        let params = Parameters::default();
+        assert_eq!(params.p, params.cofactor() * &params.q + 1u32);
+        assert!(params.g.modpow(&params.q, &params.p).is_one());
+
+        let params = Parameters::challenge_58();
+        assert_eq!(params.p, params.cofactor() * &params.q + 1u32);
+        assert!(params.g.modpow(&params.q, &params.p).is_one());
+    }
+
+    #[test]
+    fn inverts_units_only() {
+        let params = Parameters::default();
+        assert_eq!(None, params.invert(&BigUint::zero()));
+        assert_eq!(None, params.invert(&params.p));
+
+        let inv = params.invert(&(&params.g + &params.p));
+        assert_eq!(
+            Some(BigUint::one()),
+            inv.map(|inv| params.combine(&params.g, &inv))
+        );
+    }
+
+    #[test]
+    fn agrees_on_shared_secret() {
+        let alice = PrivateKey::generate(Parameters::default());
+        let bob = PrivateKey::generate(Parameters::default());
+        assert_eq!(alice.shared_secret(&bob.y), bob.shared_secret(&alice.y));
+    }
+}
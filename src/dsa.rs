@@ -0,0 +1,392 @@
+use std::{fmt::Debug, marker::PhantomData, str::FromStr};
+
+use num_bigint::BigUint;
+use num_traits::{Num, One, Zero};
+use rand::Rng;
+use sha1::{Digest, Sha1};
+
+use crate::{
+    Data, Error, Result,
+    error::ParseError,
+    math::{constant, invmod, random_below},
+};
+
+pub mod der;
+
+const P: &str = "800000000000000089e1855218a0e7dac38136ffafa72eda7859f2171e25e65eac698c1702578b07dc2a1076da241c76c62d374d8389ea5aeffd3226a0530cc565f3bf6b50929139ebeac04f48c3c84afb796d61e5a4f9a8fda812ab59494232c7d2b4deb50aa18ee9e132bfa85ac4374d7f9091abc3d015efc871a584471bb1";
+const Q: &str = "f4f47f05794b256174bba6e9b396a7707e563c5b";
+const G: &str = "5958c9d3898b224b12672c0b98e06c60df923cb8bc999d119458fef538b8fa4046c8db53039db620c094c9fa077ef389b5322a559946a71903f990f1f7e0e025e2d7f7cf494aff1a0470f5b64c36b625a097f1651fe775323556fe00b3608c887892878480e99041be601a62166ca6894bdd41a7054ec89f756ba9fc95302291";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameters {
+    pub p: BigUint,
+    pub q: BigUint,
+    pub g: BigUint,
+}
+
+impl Parameters {
+    #[must_use]
+    pub fn new(p: BigUint, q: BigUint, g: BigUint) -> Self {
+        Self { p, q, g }
+    }
+}
+
+/// The domain parameters used throughout the Cryptopals DSA challenges.
+impl Default for Parameters {
+    fn default() -> Self {
+        Self::new(constant(P, 16), constant(Q, 16), constant(G, 16))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub r: BigUint,
+    pub s: BigUint,
+}
+
+impl Signature {
+    #[must_use]
+    pub fn new(r: BigUint, s: BigUint) -> Self {
+        Self { r, s }
+    }
+}
+
+/// Parses the challenge text format: one `r` and one `s` line, each as `name = decimal` or
+/// `name: decimal`.
+impl FromStr for Signature {
+    type Err = crate::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut r = None;
+        let mut s = None;
+
+        for (key, value) in fields(input) {
+            match key {
+                "r" => r = Some(value.trim().parse().map_err(ParseError::from)?),
+                "s" => s = Some(value.trim().parse().map_err(ParseError::from)?),
+                _ => {}
+            }
+        }
+
+        let r = r.ok_or(ParseError::MissingField("r"))?;
+        let s = s.ok_or(ParseError::MissingField("s"))?;
+        Ok(Self::new(r, s))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SignedMessage {
+    pub message: Data,
+    pub hash: BigUint,
+    pub signature: Signature,
+}
+
+impl SignedMessage {
+    /// Parses the Challenge 44 format: repeated `msg`, `s`, `r` and `m` lines, where `m` is the hex
+    /// SHA-1 of the message. A record without `m` is hashed from `msg`.
+    pub fn parse_all(input: &str) -> Result<Vec<Self>> {
+        let mut records: Vec<Vec<(&str, &str)>> = vec![];
+        for (key, value) in fields(input) {
+            if key == "msg" {
+                records.push(vec![]);
+            }
+
+            let Some(record) = records.last_mut() else {
+                return Err(ParseError::MissingField("msg").into());
+            };
+            record.push((key, value));
+        }
+
+        records
+            .iter()
+            .map(|record| Self::from_fields(record))
+            .collect()
+    }
+
+    fn from_fields(fields: &[(&str, &str)]) -> Result<Self> {
+        let field = |name| {
+            fields
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+        };
+
+        let message = Data::from(field("msg").unwrap_or_default().as_bytes());
+        let parse = |name| -> Result<BigUint> {
+            let value = field(name).ok_or(ParseError::MissingField(name))?;
+            Ok(value.trim().parse().map_err(ParseError::from)?)
+        };
+        let signature = Signature::new(parse("r")?, parse("s")?);
+        let hash = match field("m") {
+            Some(m) => BigUint::from_str_radix(m.trim(), 16).map_err(ParseError::from)?,
+            None => hash(&message),
+        };
+
+        Ok(Self {
+            message,
+            hash,
+            signature,
+        })
+    }
+}
+
+/// Splits `name: value` or `name = value` lines at the first separator. Only the single space after
+/// the separator is removed from the value.
+fn fields(s: &str) -> impl Iterator<Item = (&str, &str)> {
+    s.lines().filter_map(|line| {
+        let i = line.find([':', '='])?;
+        let key = line[..i].trim();
+        let value = &line[i + 1..];
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        Some((key, value))
+    })
+}
+
+/// How much a key trusts its domain parameters and the signatures it is handed.
+pub trait Validation: Debug + Clone + Copy + PartialEq + Eq {
+    fn valid_parameters(params: &Parameters) -> bool;
+    fn valid_r(r: &BigUint, q: &BigUint) -> bool;
+}
+
+/// Rejects malformed domain parameters and requires `0 < r < q`, as the standard demands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Strict;
+
+/// Trusts whatever domain parameters it is given and accepts any `r < q`, including zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Lenient;
+
+impl Validation for Strict {
+    fn valid_parameters(params: &Parameters) -> bool {
+        let Parameters { p, q, g } = params;
+        g > &BigUint::one() && g < p && g.modpow(q, p).is_one()
+    }
+
+    fn valid_r(r: &BigUint, q: &BigUint) -> bool {
+        !r.is_zero() && r < q
+    }
+}
+
+impl Validation for Lenient {
+    fn valid_parameters(_: &Parameters) -> bool {
+        true
+    }
+
+    fn valid_r(r: &BigUint, q: &BigUint) -> bool {
+        r < q
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey<V: Validation = Strict> {
+    pub params: Parameters,
+    pub y: BigUint,
+    validation: PhantomData<V>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivateKey<V: Validation = Strict> {
+    pub params: Parameters,
+    pub x: BigUint,
+    pub y: BigUint,
+    validation: PhantomData<V>,
+}
+
+#[must_use]
+pub fn hash(message: &Data) -> BigUint {
+    BigUint::from_bytes_be(&Sha1::digest(message))
+}
+
+/// The SHA-1 of the private key's lowercase hex representation, as published by the challenges.
+#[must_use]
+pub fn fingerprint(x: &BigUint) -> Data {
+    let hex = x.to_str_radix(16);
+    Data::from(Sha1::digest(hex).as_slice())
+}
+
+impl<V: Validation> PublicKey<V> {
+    #[must_use]
+    pub fn new(params: Parameters, y: BigUint) -> Self {
+        Self {
+            params,
+            y,
+            validation: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn verify(&self, message: &Data, signature: &Signature) -> bool {
+        let Parameters { p, q, g } = &self.params;
+        let Signature { r, s } = signature;
+
+        if !V::valid_parameters(&self.params) || !V::valid_r(r, q) || s.is_zero() || s >= q {
+            return false;
+        }
+
+        let Some(w) = invmod(s, q) else {
+            return false;
+        };
+        let u1 = (hash(message) * &w) % q;
+        let u2 = (r * &w) % q;
+        let v = ((g.modpow(&u1, p) * self.y.modpow(&u2, p)) % p) % q;
+
+        &v == r
+    }
+}
+
+impl<V: Validation> PrivateKey<V> {
+    pub fn generate(params: Parameters) -> Result<Self> {
+        Self::generate_with_rng(params, &mut rand::rng())
+    }
+
+    pub fn generate_with_rng<R: Rng + ?Sized>(params: Parameters, rng: &mut R) -> Result<Self> {
+        let x = loop {
+            let x = random_below(&params.q, rng);
+            if !x.is_zero() {
+                break x;
+            }
+        };
+
+        Self::from_x(params, x)
+    }
+
+    pub fn from_x(params: Parameters, x: BigUint) -> Result<Self> {
+        if !V::valid_parameters(&params) {
+            return Err(Error::InvalidKey("invalid domain parameters"));
+        }
+
+        let y = params.g.modpow(&x, &params.p);
+        Ok(Self {
+            params,
+            x,
+            y,
+            validation: PhantomData,
+        })
+    }
+
+    #[must_use]
+    pub fn public_key(&self) -> PublicKey<V> {
+        PublicKey::new(self.params.clone(), self.y.clone())
+    }
+
+    #[must_use]
+    pub fn sign(&self, message: &Data) -> Signature {
+        let mut rng = rand::rng();
+        loop {
+            let k = random_below(&self.params.q, &mut rng);
+            if let Some(signature) = self.sign_with_k(message, &k) {
+                break signature;
+            }
+        }
+    }
+
+    /// Signs with a caller-chosen nonce, or returns `None` if `k` yields a degenerate signature.
+    #[must_use]
+    pub fn sign_with_k(&self, message: &Data, k: &BigUint) -> Option<Signature> {
+        let Parameters { p, q, g } = &self.params;
+
+        let r = g.modpow(k, p) % q;
+        if !V::valid_r(&r, q) {
+            return None;
+        }
+
+        let k_inv = invmod(k, q)?;
+        let s = (k_inv * (hash(message) + &self.x * &r)) % q;
+        if s.is_zero() {
+            return None;
+        }
+
+        Some(Signature::new(r, s))
+    }
+}
+
+/// Recovers `x = (s * k - H(m)) / r mod q` from a signature and the nonce that produced it.
+#[must_use]
+pub fn recover_private_key(
+    params: &Parameters,
+    hash: &BigUint,
+    signature: &Signature,
+    k: &BigUint,
+) -> Option<BigUint> {
+    let q = &params.q;
+    let r_inv = invmod(&signature.r, q)?;
+    let sk = (&signature.s * k) % q;
+    let h = hash % q;
+    let x = ((sk + q - h) * r_inv) % q;
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::{IntoDiagnostic, Result};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn sign_and_verify() -> Result<()> {
+        let key: PrivateKey = PrivateKey::generate(Parameters::default())?;
+        let public = key.public_key();
+        let message = Data::from("hello, world!".as_bytes());
+
+        let signature = key.sign(&message);
+        assert!(public.verify(&message, &signature));
+        assert!(!public.verify(&Data::from("goodbye, world!".as_bytes()), &signature));
+
+        Ok(())
+    }
+
+    #[test]
+    fn recovers_key_from_known_nonce() -> Result<()> {
+        let key: PrivateKey = PrivateKey::generate(Parameters::default())?;
+        let message = Data::from("hello, world!".as_bytes());
+        let k = BigUint::from(12345u32);
+
+        let signature = key.sign_with_k(&message, &k).unwrap();
+        let res = recover_private_key(&key.params, &hash(&message), &signature, &k);
+        assert_eq!(Some(key.x), res);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_signed_messages() -> Result<()> {
+        let input = "msg: Listen for me, you better listen for me now. \ns: 1267396447369736888040262262183731677867615804316\nr: 1105520928110492191417703162650245113664610474875\nm: a4db3de27e2db3e5ef085ced2bced91b82e0df19\nmsg: Listen for me, you better listen for me now. \ns: 29097472083055673620219739525237952924429516683\nr: 51241962016175933742870323080382366896234169532\n";
+        let res = SignedMessage::parse_all(input)?;
+
+        assert_eq!(2, res.len());
+        assert_eq!(
+            "Listen for me, you better listen for me now. ",
+            res[0].message
+        );
+        assert_eq!(
+            "a4db3de27e2db3e5ef085ced2bced91b82e0df19",
+            res[0].hash.to_str_radix(16)
+        );
+        assert_eq!(hash(&res[1].message), res[1].hash);
+        assert_eq!(
+            "51241962016175933742870323080382366896234169532",
+            res[1].signature.r.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_signature() -> Result<()> {
+        let signature: Signature = "r = 548099063082341131477253921760299949438196259240\ns = 857042759984254168557880549501802188789837994940\n".parse()?;
+        assert_eq!(
+            Signature::new(
+                "548099063082341131477253921760299949438196259240"
+                    .parse()
+                    .into_diagnostic()?,
+                "857042759984254168557880549501802188789837994940"
+                    .parse()
+                    .into_diagnostic()?,
+            ),
+            signature
+        );
+
+        Ok(())
+    }
+}
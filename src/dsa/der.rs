@@ -0,0 +1,148 @@
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use super::{Parameters, PrivateKey, PublicKey, Signature, Validation};
+use crate::{
+    Data, Error, Result,
+    der::{DSA, Der},
+};
+
+impl Parameters {
+    /// `AlgorithmIdentifier { id-dsa, Dss-Parms ::= SEQUENCE { p, q, g } }`
+    fn algorithm(&self) -> Der {
+        Der::Sequence(vec![
+            Der::ObjectIdentifier(DSA.to_vec()),
+            Der::Sequence(vec![(&self.p).into(), (&self.q).into(), (&self.g).into()]),
+        ])
+    }
+
+    fn from_algorithm(der: &Der) -> Result<Self> {
+        let [oid, params] = der.as_sequence()? else {
+            return Err(Error::InvalidEncoding);
+        };
+        if oid.as_oid()? != DSA {
+            return Err(Error::InvalidKey("not a DSA key"));
+        }
+
+        let [p, q, g] = params.as_sequence()? else {
+            return Err(Error::InvalidEncoding);
+        };
+        Ok(Self::new(
+            p.as_integer()?.clone(),
+            q.as_integer()?.clone(),
+            g.as_integer()?.clone(),
+        ))
+    }
+}
+
+impl Signature {
+    /// `Dss-Sig-Value ::= SEQUENCE { r, s }`
+    #[must_use]
+    pub fn der(&self) -> Data {
+        Der::Sequence(vec![(&self.r).into(), (&self.s).into()]).encode()
+    }
+
+    pub fn from_der(data: &Data) -> Result<Self> {
+        let der = Der::decode(data)?;
+        let [r, s] = der.as_sequence()? else {
+            return Err(Error::InvalidEncoding);
+        };
+
+        Ok(Self::new(r.as_integer()?.clone(), s.as_integer()?.clone()))
+    }
+}
+
+impl<V: Validation> PublicKey<V> {
+    /// `SubjectPublicKeyInfo ::= SEQUENCE { algorithm, BIT STRING { y } }`
+    #[must_use]
+    pub fn spki_der(&self) -> Data {
+        Der::Sequence(vec![
+            self.params.algorithm(),
+            Der::BitString {
+                unused: 0,
+                data: Der::from(&self.y).encode(),
+            },
+        ])
+        .encode()
+    }
+
+    pub fn from_spki_der(data: &Data) -> Result<Self> {
+        let der = Der::decode(data)?;
+        let [algorithm, key] = der.as_sequence()? else {
+            return Err(Error::InvalidEncoding);
+        };
+
+        let params = Parameters::from_algorithm(algorithm)?;
+        let y = Der::decode(key.as_bit_string()?)?.as_integer()?.clone();
+        Ok(Self::new(params, y))
+    }
+}
+
+impl<V: Validation> PrivateKey<V> {
+    /// `PrivateKeyInfo ::= SEQUENCE { version, algorithm, OCTET STRING { x } }`
+    #[must_use]
+    pub fn pkcs8_der(&self) -> Data {
+        Der::Sequence(vec![
+            BigUint::zero().into(),
+            self.params.algorithm(),
+            Der::OctetString(Der::from(&self.x).encode()),
+        ])
+        .encode()
+    }
+
+    pub fn from_pkcs8_der(data: &Data) -> Result<Self> {
+        let der = Der::decode(data)?;
+        let [version, algorithm, key] = der.as_sequence()? else {
+            return Err(Error::InvalidEncoding);
+        };
+        if !version.as_integer()?.is_zero() {
+            return Err(Error::InvalidEncoding);
+        }
+
+        let params = Parameters::from_algorithm(algorithm)?;
+        let x = Der::decode(key.as_octet_string()?)?.as_integer()?.clone();
+        Self::from_x(params, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn reads_and_writes_openssl_keys() -> Result<()> {
+        let pkcs8 = include_str!("../../data/dsa_pkcs8.pem");
+        let spki = include_str!("../../data/dsa_spki.pem");
+
+        let key: PrivateKey = PrivateKey::from_pkcs8_der(&Data::from_pem(pkcs8, "PRIVATE KEY")?)?;
+        assert_eq!(pkcs8, key.pkcs8_der().pem("PRIVATE KEY"));
+
+        let public = PublicKey::from_spki_der(&Data::from_pem(spki, "PUBLIC KEY")?)?;
+        assert_eq!(key.public_key(), public);
+        assert_eq!(spki, public.spki_der().pem("PUBLIC KEY"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn verifies_openssl_signature() -> Result<()> {
+        let spki = include_str!("../../data/dsa_spki.pem");
+        let public: PublicKey = PublicKey::from_spki_der(&Data::from_pem(spki, "PUBLIC KEY")?)?;
+
+        let sig = Data::from_base64(include_str!("../../data/dsa_sha1_sig.b64").trim())?;
+        let sig = Signature::from_der(&sig)?;
+        assert_eq!(
+            sig.der(),
+            Data::from_base64(include_str!("../../data/dsa_sha1_sig.b64").trim())?
+        );
+
+        let msg = Data::from(b"hi mom".as_slice());
+        assert!(public.verify(&msg, &sig));
+        assert!(!public.verify(&Data::from(b"hi dad".as_slice()), &sig));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,443 @@
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use rand::Rng;
+
+use crate::{
+    Error, Result,
+    group::Group,
+    math::{constant, invmod, random_below},
+};
+
+const P: &str = "233970423115425145524320034830162017933";
+const A: i64 = -95051;
+const B: i64 = 11_279_326;
+const GX: &str = "182";
+const GY: &str = "85518893674295321206118380980485522083";
+const Q: &str = "29246302889428143187362802287225875743";
+
+/// A point in affine coordinates, or the point at infinity.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Point {
+    Infinity,
+    Affine { x: BigUint, y: BigUint },
+}
+
+impl Point {
+    #[must_use]
+    pub fn new(x: BigUint, y: BigUint) -> Self {
+        Self::Affine { x, y }
+    }
+}
+
+/// A point `(x : y : z)` in homogeneous projective coordinates, standing for `(x / z, y / z)`, or
+/// for the point at infinity when `z = 0`. Adding points this way needs no field inversions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Projective {
+    pub x: BigUint,
+    pub y: BigUint,
+    pub z: BigUint,
+}
+
+/// The curve `y^2 = x^3 + ax + b` over the integers modulo a prime `p`, with a base point `g` of
+/// order `q`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Curve {
+    pub p: BigUint,
+    pub a: BigUint,
+    pub b: BigUint,
+    pub g: Point,
+    pub q: BigUint,
+}
+
+impl Curve {
+    #[must_use]
+    pub fn new(p: BigUint, a: BigUint, b: BigUint, g: Point, q: BigUint) -> Self {
+        Self { p, a, b, g, q }
+    }
+
+    /// The curve from Challenge 59, `y^2 = x^3 - 95051x + 11279326`.
+    #[must_use]
+    pub fn challenge_59() -> Self {
+        let p = constant(P, 10);
+        let a = Self::coefficient(&p, A);
+        let b = Self::coefficient(&p, B);
+        Self::new(
+            p,
+            a,
+            b,
+            Point::new(constant(GX, 10), constant(GY, 10)),
+            constant(Q, 10),
+        )
+    }
+
+    /// Reduces a possibly negative coefficient modulo `p`.
+    #[must_use]
+    pub fn coefficient(p: &BigUint, n: i64) -> BigUint {
+        let abs = BigUint::from(n.unsigned_abs()) % p;
+        if n < 0 && !abs.is_zero() {
+            p - abs
+        } else {
+            abs
+        }
+    }
+
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a + &self.p - b % &self.p) % &self.p
+    }
+
+    /// Divides modulo `p`, or [`None`] when the divisor is a multiple of `p`.
+    fn div(&self, a: &BigUint, b: &BigUint) -> Option<BigUint> {
+        let inv = invmod(&(b % &self.p), &self.p)?;
+        Some(a * inv % &self.p)
+    }
+
+    /// Reduces the coordinates modulo `p`, so that points from outside compare and test for zero
+    /// like the ones computed here.
+    #[must_use]
+    pub fn reduce(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine { x, y } => Point::new(x % &self.p, y % &self.p),
+        }
+    }
+
+    fn reduce_projective(&self, point: &Projective) -> Projective {
+        Projective {
+            x: &point.x % &self.p,
+            y: &point.y % &self.p,
+            z: &point.z % &self.p,
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: &Point) -> bool {
+        match point {
+            Point::Infinity => true,
+            Point::Affine { x, y } => {
+                let lhs = y * y % &self.p;
+                let rhs = (x * x * x + &self.a * x + &self.b) % &self.p;
+                x < &self.p && y < &self.p && lhs == rhs
+            }
+        }
+    }
+
+    /// Checks that a public point lies on the curve, in the subgroup generated by `g`, and isn't
+    /// the identity.
+    pub fn validate(&self, point: &Point) -> Result<()> {
+        if *point == Point::Infinity {
+            return Err(Error::InvalidKey("point at infinity"));
+        }
+        if !self.contains(point) {
+            return Err(Error::InvalidKey("point is not on the curve"));
+        }
+        if self.scale(point, &self.q) != Point::Infinity {
+            return Err(Error::InvalidKey("point is outside the subgroup"));
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn neg(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine { x, y } => Point::new(x % &self.p, self.sub(&BigUint::zero(), y)),
+        }
+    }
+
+    #[must_use]
+    pub fn add(&self, lhs: &Point, rhs: &Point) -> Point {
+        let (lhs, rhs) = (self.reduce(lhs), self.reduce(rhs));
+        let (Point::Affine { x: x1, y: y1 }, Point::Affine { x: x2, y: y2 }) = (&lhs, &rhs) else {
+            return if lhs == Point::Infinity { rhs } else { lhs };
+        };
+
+        if x1 == x2 {
+            return if (y1 + y2) % &self.p == BigUint::zero() {
+                Point::Infinity
+            } else {
+                self.double(&lhs)
+            };
+        }
+
+        // the x coordinates are distinct mod p, so this only fails if p isn't prime
+        let Some(m) = self.div(&self.sub(y2, y1), &self.sub(x2, x1)) else {
+            return Point::Infinity;
+        };
+        let x3 = self.sub(&self.sub(&(&m * &m), x1), x2);
+        let y3 = self.sub(&(m * self.sub(x1, &x3)), y1);
+        Point::new(x3, y3)
+    }
+
+    /// Doubles a point, giving the point at infinity when the tangent is vertical.
+    #[must_use]
+    pub fn double(&self, point: &Point) -> Point {
+        let Point::Affine { x, y } = self.reduce(point) else {
+            return Point::Infinity;
+        };
+        let Some(m) = self.div(&(3u32 * &x * &x + &self.a), &(2u32 * &y)) else {
+            return Point::Infinity;
+        };
+        let x3 = self.sub(&(&m * &m), &(2u32 * &x));
+        let y3 = self.sub(&(m * self.sub(&x, &x3)), &y);
+        Point::new(x3, y3)
+    }
+
+    #[must_use]
+    pub fn to_projective(&self, point: &Point) -> Projective {
+        match point {
+            Point::Infinity => Projective {
+                x: BigUint::zero(),
+                y: BigUint::one(),
+                z: BigUint::zero(),
+            },
+            Point::Affine { x, y } => Projective {
+                x: x % &self.p,
+                y: y % &self.p,
+                z: BigUint::one(),
+            },
+        }
+    }
+
+    #[must_use]
+    pub fn to_affine(&self, point: &Projective) -> Point {
+        let (Some(x), Some(y)) = (self.div(&point.x, &point.z), self.div(&point.y, &point.z))
+        else {
+            return Point::Infinity;
+        };
+        Point::new(x, y)
+    }
+
+    #[must_use]
+    pub fn add_projective(&self, lhs: &Projective, rhs: &Projective) -> Projective {
+        let (lhs, rhs) = (&self.reduce_projective(lhs), &self.reduce_projective(rhs));
+        if lhs.z.is_zero() {
+            return rhs.clone();
+        }
+        if rhs.z.is_zero() {
+            return lhs.clone();
+        }
+
+        let p = &self.p;
+        let y1z2 = &lhs.y * &rhs.z % p;
+        let x1z2 = &lhs.x * &rhs.z % p;
+        let z1z2 = &lhs.z * &rhs.z % p;
+        let u = self.sub(&(&rhs.y * &lhs.z), &y1z2);
+        let v = self.sub(&(&rhs.x * &lhs.z), &x1z2);
+        if v.is_zero() {
+            return if u.is_zero() {
+                self.double_projective(lhs)
+            } else {
+                self.to_projective(&Point::Infinity)
+            };
+        }
+
+        let vv = &v * &v % p;
+        let vvv = &vv * &v % p;
+        let r = &vv * x1z2 % p;
+        let w = self.sub(&(&u * &u * &z1z2), &(&vvv + 2u32 * &r));
+        Projective {
+            x: &v * &w % p,
+            y: self.sub(&(u * self.sub(&r, &w)), &(&vvv * y1z2)),
+            z: vvv * z1z2 % p,
+        }
+    }
+
+    #[must_use]
+    pub fn double_projective(&self, point: &Projective) -> Projective {
+        let point = self.reduce_projective(point);
+        if point.z.is_zero() || point.y.is_zero() {
+            return self.to_projective(&Point::Infinity);
+        }
+
+        let p = &self.p;
+        let Projective { x, y, z } = &point;
+        let w = (&self.a * z * z + 3u32 * x * x) % p;
+        let s = y * z % p;
+        let b = x * y * &s % p;
+        let h = self.sub(&(&w * &w), &(8u32 * &b));
+        Projective {
+            x: 2u32 * &h * &s % p,
+            y: self.sub(&(w * self.sub(&(4u32 * b), &h)), &(8u32 * y * y * &s * &s)),
+            z: 8u32 * &s * &s * s % p,
+        }
+    }
+}
+
+/// The subgroup of order `q` generated by `g`, written multiplicatively so that the attacks on
+/// finite-field DH carry over.
+impl Group for Curve {
+    type Element = Point;
+
+    fn identity(&self) -> Point {
+        Point::Infinity
+    }
+
+    fn generator(&self) -> Point {
+        self.g.clone()
+    }
+
+    fn order(&self) -> &BigUint {
+        &self.q
+    }
+
+    fn combine(&self, a: &Point, b: &Point) -> Point {
+        self.add(a, b)
+    }
+
+    fn invert(&self, a: &Point) -> Option<Point> {
+        Some(self.neg(a))
+    }
+
+    /// Double-and-add in projective coordinates, converting back to affine once at the end.
+    fn scale(&self, a: &Point, n: &BigUint) -> Point {
+        let a = self.to_projective(a);
+        let mut res = self.to_projective(&Point::Infinity);
+        for i in (0..n.bits()).rev() {
+            res = self.double_projective(&res);
+            if n.bit(i) {
+                res = self.add_projective(&res, &a);
+            }
+        }
+        self.to_affine(&res)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    pub curve: Curve,
+    pub point: Point,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivateKey {
+    pub curve: Curve,
+    pub d: BigUint,
+    pub point: Point,
+}
+
+impl PublicKey {
+    #[must_use]
+    pub fn new(curve: Curve, point: Point) -> Self {
+        Self { curve, point }
+    }
+}
+
+impl PrivateKey {
+    #[must_use]
+    pub fn generate(curve: Curve) -> Self {
+        Self::generate_with_rng(curve, &mut rand::rng())
+    }
+
+    #[must_use]
+    pub fn generate_with_rng<R: Rng + ?Sized>(curve: Curve, rng: &mut R) -> Self {
+        let d = loop {
+            let d = random_below(&curve.q, rng);
+            if !d.is_zero() {
+                break d;
+            }
+        };
+
+        Self::from_d(curve, d)
+    }
+
+    #[must_use]
+    pub fn from_d(curve: Curve, d: BigUint) -> Self {
+        let point = curve.scale(&curve.g, &d);
+        Self { curve, d, point }
+    }
+
+    #[must_use]
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::new(self.curve.clone(), self.point.clone())
+    }
+
+    /// Multiplies the other party's public point by our secret, without checking that it lies on
+    /// the curve at all.
+    #[must_use]
+    pub fn shared_secret(&self, point: &Point) -> Point {
+        self.curve.scale(point, &self.d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn challenge_59_curve_is_consistent() {
+        let curve = Curve::challenge_59();
+        assert!(curve.contains(&curve.g));
+        assert!(curve.validate(&curve.g).is_ok());
+        assert_eq!(Point::Infinity, curve.scale(&curve.g, &curve.q));
+    }
+
+    #[test]
+    fn affine_and_projective_agree() {
+        let mut rng = StdRng::seed_from_u64(37);
+        let curve = Curve::challenge_59();
+        let n = random_below(&curve.q, &mut rng);
+
+        let mut expected = Point::Infinity;
+        for i in (0..n.bits()).rev() {
+            expected = curve.double(&expected);
+            if n.bit(i) {
+                expected = curve.add(&expected, &curve.g);
+            }
+        }
+
+        let res = curve.scale(&curve.g, &n);
+        assert_eq!(expected, res);
+        assert!(curve.contains(&res));
+
+        let sum = curve.add(&res, &curve.neg(&res));
+        assert_eq!(Point::Infinity, sum);
+    }
+
+    #[test]
+    fn rejects_invalid_points() {
+        let curve = Curve::challenge_59();
+        let Point::Affine { x, y } = curve.g.clone() else {
+            unreachable!()
+        };
+
+        assert!(curve.validate(&Point::Infinity).is_err());
+        assert!(curve.validate(&Point::new(x, y + 1u32)).is_err());
+    }
+
+    #[test]
+    fn reduces_coordinates_from_outside() {
+        let curve = Curve::challenge_59();
+        let Point::Affine { x, y } = curve.g.clone() else {
+            unreachable!()
+        };
+        let unreduced = Point::new(&x + &curve.p, &y + &curve.p);
+
+        assert_eq!(
+            Point::Infinity,
+            curve.double(&Point::new(x.clone(), curve.p.clone()))
+        );
+        assert_eq!(curve.double(&curve.g), curve.add(&curve.g, &unreduced));
+        assert_eq!(Point::Infinity, curve.add(&curve.neg(&curve.g), &unreduced));
+
+        let key = PrivateKey::generate(curve.clone());
+        assert_eq!(key.point, key.shared_secret(&unreduced));
+        assert_eq!(
+            key.shared_secret(&Point::new(x.clone(), BigUint::zero())),
+            key.shared_secret(&Point::new(x, curve.p.clone()))
+        );
+    }
+
+    #[test]
+    fn agrees_on_shared_secret() {
+        let alice = PrivateKey::generate(Curve::challenge_59());
+        let bob = PrivateKey::generate(Curve::challenge_59());
+        assert!(alice.curve.validate(&bob.point).is_ok());
+        assert_eq!(
+            alice.shared_secret(&bob.point),
+            bob.shared_secret(&alice.point)
+        );
+    }
+}
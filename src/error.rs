@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 use miette::Diagnostic;
+use num_bigint::BigUint;
 use thiserror::Error;
 
 pub type Result<T> = core::result::Result<T, Error>;
@@ -19,6 +20,39 @@ pub enum Error {
 
     #[error("Invalid padding (padding byte: `{0}`)")]
     InvalidPadding(u8),
+
+    #[error("Invalid key ({0})")]
+    InvalidKey(&'static str),
+
+    #[error("Message representative out of range")]
+    MessageOutOfRange,
+
+    #[error("Moduli are not pairwise coprime (shared factor: `{0}`)")]
+    NotCoprime(BigUint),
+
+    #[error("Division by zero")]
+    DivisionByZero,
+
+    #[error("Expected `{expected}` ciphertexts, got `{actual}`")]
+    CiphertextCount { expected: usize, actual: usize },
+
+    #[error("Value is not a perfect power (exponent: `{0}`)")]
+    NotPerfectPower(u32),
+
+    #[error("Oracle refused to answer the query")]
+    Refused,
+
+    #[error("Invalid encoding")]
+    InvalidEncoding,
+
+    #[error("Invalid MAC")]
+    InvalidMac,
+
+    #[error("Attack failed ({0})")]
+    AttackFailed(&'static str),
+
+    #[error("No convergent revealed the key (tried `{tried}`, denominators up to `{bound}`)")]
+    NoConvergent { tried: usize, bound: BigUint },
 }
 
 #[derive(Error, Debug, Diagnostic, Clone)]
@@ -28,6 +62,15 @@ pub enum ParseError {
 
     #[error(transparent)]
     Base64(#[from] base64::DecodeError),
+
+    #[error(transparent)]
+    BigInt(#[from] num_bigint::ParseBigIntError),
+
+    #[error("Missing field `{0}`")]
+    MissingField(&'static str),
+
+    #[error("Missing PEM block `{0}`")]
+    MissingPemBlock(String),
 }
 
 #[derive(Debug, Clone)]
@@ -35,6 +78,8 @@ pub enum InvalidLengthType {
     Block,
     Key,
     IV,
+    Message,
+    State,
 }
 
 impl Display for InvalidLengthType {
@@ -43,6 +88,8 @@ impl Display for InvalidLengthType {
             InvalidLengthType::Block => "block",
             InvalidLengthType::Key => "key",
             InvalidLengthType::IV => "IV",
+            InvalidLengthType::Message => "message",
+            InvalidLengthType::State => "state",
         };
 
         write!(f, "{s}")
@@ -0,0 +1,33 @@
+use std::{fmt::Debug, hash::Hash};
+
+use num_bigint::BigUint;
+
+/// A cyclic group written multiplicatively, with a generator of known order.
+pub trait Group {
+    type Element: Clone + Eq + Hash + Debug;
+
+    fn identity(&self) -> Self::Element;
+
+    fn generator(&self) -> Self::Element;
+
+    /// The order of [`Group::generator`].
+    fn order(&self) -> &BigUint;
+
+    fn combine(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    /// The inverse of `a`, or [`None`] when `a` isn't a unit, which only happens for elements
+    /// from outside the group.
+    fn invert(&self, a: &Self::Element) -> Option<Self::Element>;
+
+    /// Combines `a` with itself `n` times, by square-and-multiply unless the group knows better.
+    fn scale(&self, a: &Self::Element, n: &BigUint) -> Self::Element {
+        let mut res = self.identity();
+        for i in (0..n.bits()).rev() {
+            res = self.combine(&res, &res);
+            if n.bit(i) {
+                res = self.combine(&res, a);
+            }
+        }
+        res
+    }
+}
@@ -0,0 +1,4 @@
+pub mod md4;
+pub mod merkle_damgard;
+
+pub use merkle_damgard::{Compress, DaviesMeyer, MerkleDamgard};
@@ -0,0 +1,165 @@
+use crate::Data;
+
+pub const INITIAL_STATE: [u32; 4] = [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476];
+
+/// Rotation amounts for each round, cycling every four steps.
+pub const SHIFTS: [[u32; 4]; 3] = [[3, 7, 11, 19], [3, 5, 9, 13], [3, 9, 11, 15]];
+
+/// Additive constants for each round.
+pub const CONSTANTS: [u32; 3] = [0, 0x5a82_7999, 0x6ed9_eba1];
+
+/// The message word used by each step of each round.
+pub const ORDER: [[usize; 16]; 3] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15],
+    [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15],
+];
+
+#[must_use]
+pub fn f(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (!x & z)
+}
+
+#[must_use]
+pub fn g(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (x & z) | (y & z)
+}
+
+#[must_use]
+pub fn h(x: u32, y: u32, z: u32) -> u32 {
+    x ^ y ^ z
+}
+
+/// The boolean function of each round.
+pub const FUNCTIONS: [fn(u32, u32, u32) -> u32; 3] = [f, g, h];
+
+/// The register value computed by `step` (from 0 to 47), given the four values before it, oldest
+/// first: `(a + fn(b, c, d) + m + k) <<< s`.
+#[must_use]
+pub fn step(step: usize, [a, d, c, b]: [u32; 4], block: &[u32; 16]) -> u32 {
+    let (round, i) = (step / 16, step % 16);
+    let m = block[ORDER[round][i]];
+    a.wrapping_add(FUNCTIONS[round](b, c, d))
+        .wrapping_add(m)
+        .wrapping_add(CONSTANTS[round])
+        .rotate_left(SHIFTS[round][i % 4])
+}
+
+/// Solves [`step`] for the message word that produces `value`.
+#[must_use]
+pub fn message_word(step: usize, [a, d, c, b]: [u32; 4], value: u32) -> u32 {
+    let (round, i) = (step / 16, step % 16);
+    value
+        .rotate_right(SHIFTS[round][i % 4])
+        .wrapping_sub(a)
+        .wrapping_sub(FUNCTIONS[round](b, c, d))
+        .wrapping_sub(CONSTANTS[round])
+}
+
+/// Every register value the compression function computes, preceded by the initial `a, d, c, b`,
+/// so that step `i` writes index `i + 4` from the four before it.
+#[must_use]
+pub fn states(state: [u32; 4], block: &[u32; 16]) -> [u32; 52] {
+    let [a, b, c, d] = state;
+    let mut q = [0; 52];
+    q[..4].copy_from_slice(&[a, d, c, b]);
+
+    for i in 0..48 {
+        let prev = q[i..i + 4].try_into().expect("four registers");
+        q[i + 4] = step(i, prev, block);
+    }
+    q
+}
+
+#[must_use]
+pub fn compress(state: [u32; 4], block: &[u32; 16]) -> [u32; 4] {
+    let q = states(state, block);
+    let [a, b, c, d] = state;
+    [
+        a.wrapping_add(q[48]),
+        b.wrapping_add(q[51]),
+        c.wrapping_add(q[50]),
+        d.wrapping_add(q[49]),
+    ]
+}
+
+/// Reads a block as sixteen little-endian words.
+#[must_use]
+pub fn words(block: &[u8]) -> [u32; 16] {
+    let mut words = [0; 16];
+    for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().expect("four bytes"));
+    }
+    words
+}
+
+#[must_use]
+pub fn block(words: &[u32; 16]) -> Data {
+    let bytes: Box<[u8]> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+    Data::from(bytes)
+}
+
+/// The padding appended to a message of `len` bytes, ending in its bit length as a little-endian
+/// `u64`.
+#[must_use]
+pub fn padding(len: usize) -> Data {
+    let zeroes = (119 - len % 64) % 64;
+    let mut bytes = vec![0x80];
+    bytes.resize(1 + zeroes, 0);
+    bytes.extend((len as u64 * 8).to_le_bytes());
+    Data::from(bytes)
+}
+
+#[must_use]
+pub fn md4(message: &[u8]) -> Data {
+    let padded: Box<[u8]> = message
+        .iter()
+        .chain(padding(message.len()).iter())
+        .copied()
+        .collect();
+
+    let state = padded
+        .chunks_exact(64)
+        .fold(INITIAL_STATE, |state, block| compress(state, &words(block)));
+    let bytes: Box<[u8]> = state.iter().flat_map(|w| w.to_le_bytes()).collect();
+    Data::from(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use md4::{Digest, Md4};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn matches_rfc_1320() {
+        assert_eq!("31d6cfe0d16ae931b73c59d7e0c089c0", md4(b"").hex());
+        assert_eq!("a448017aaf21d8525fc10ae87aa6729d", md4(b"abc").hex());
+        assert_eq!(
+            "e33b4ddc9c38f2199c3e7b164fcc0536",
+            md4(
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890"
+            )
+            .hex()
+        );
+    }
+
+    #[test]
+    fn matches_reference() {
+        for len in 0..200 {
+            let message = vec![b'a'; len];
+            assert_eq!(md4(&message), Md4::digest(&message).as_slice());
+        }
+    }
+
+    #[test]
+    fn message_word_inverts_step() {
+        let block = words(&[0x5a; 64]);
+        let q = states(INITIAL_STATE, &block);
+        for i in 0..16 {
+            let prev = q[i..i + 4].try_into().unwrap();
+            assert_eq!(block[i], message_word(i, prev, q[i + 4]));
+        }
+    }
+}
@@ -1,12 +1,27 @@
 #![allow(clippy::missing_errors_doc, reason = "ignore docs for now")]
+#![cfg_attr(
+    test,
+    allow(
+        clippy::needless_borrows_for_generic_args,
+        reason = "baseline tests borrow their inputs"
+    )
+)]
 
 pub mod attack;
 pub mod blackbox;
 pub mod cipher;
 pub mod data;
+pub mod der;
+pub mod dh;
+pub mod dsa;
+pub mod ec;
 pub mod error;
+pub mod group;
 pub mod hamming_distance;
+pub mod hash;
+pub mod math;
 pub mod pad;
+pub mod rsa;
 
 pub use cipher::{AesCbc, AesEcb};
 pub use data::Data;
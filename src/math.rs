@@ -0,0 +1,131 @@
+use itertools::Itertools;
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_traits::{Num, One, Zero};
+use rand::Rng;
+
+use crate::{Error, Result};
+
+pub mod continued_fraction;
+pub mod polynomial;
+pub mod prime;
+
+/// Parses one of the hardcoded protocol constants.
+pub(crate) fn constant(s: &str, radix: u32) -> BigUint {
+    BigUint::from_str_radix(s, radix).expect("valid constant")
+}
+
+#[must_use]
+pub fn invmod(a: &BigUint, m: &BigUint) -> Option<BigUint> {
+    let a = BigInt::from(a.clone());
+    let m = BigInt::from(m.clone());
+    let egcd = a.extended_gcd(&m);
+
+    if egcd.gcd.is_one() {
+        egcd.x.mod_floor(&m).to_biguint()
+    } else {
+        None
+    }
+}
+
+/// Solves `x = a_i mod m_i` for each `(a_i, m_i)`, returning `x` and the product of the moduli.
+pub fn crt(residues: &[(BigUint, BigUint)]) -> Result<(BigUint, BigUint)> {
+    if residues.iter().any(|(_, m)| m.is_zero()) {
+        return Err(Error::DivisionByZero);
+    }
+    if let Some(factor) = residues
+        .iter()
+        .tuple_combinations()
+        .map(|((_, lhs), (_, rhs))| lhs.gcd(rhs))
+        .find(|gcd| !gcd.is_one())
+    {
+        return Err(Error::NotCoprime(factor));
+    }
+
+    let modulus: BigUint = residues.iter().map(|(_, m)| m).product();
+    let x = residues
+        .iter()
+        .map(|(a, m)| {
+            let ms = &modulus / m;
+            let Some(inv) = invmod(&ms, m) else {
+                unreachable!("moduli are pairwise coprime")
+            };
+            a * ms * inv
+        })
+        .sum::<BigUint>()
+        % &modulus;
+
+    Ok((x, modulus))
+}
+
+pub fn random_bits<R: Rng + ?Sized>(bits: u64, rng: &mut R) -> BigUint {
+    let len = bits.div_ceil(8);
+    let mut bytes = vec![0u8; usize::try_from(len).expect("bit count fits in memory")];
+    rng.fill_bytes(&mut bytes);
+
+    let excess = len * 8 - bits;
+    if let Some(first) = bytes.first_mut() {
+        *first &= 0xff >> excess;
+    }
+
+    BigUint::from_bytes_be(&bytes)
+}
+
+pub fn random_below<R: Rng + ?Sized>(bound: &BigUint, rng: &mut R) -> BigUint {
+    assert!(!bound.is_zero(), "bound must be positive");
+    let bits = bound.bits();
+    loop {
+        let n = random_bits(bits, rng);
+        if &n < bound {
+            return n;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn invmod_works() {
+        let res = invmod(&17u32.into(), &3120u32.into());
+        assert_eq!(Some(BigUint::from(2753u32)), res);
+
+        let res = invmod(&6u32.into(), &9u32.into());
+        assert_eq!(None, res);
+    }
+
+    #[test]
+    fn crt_works() -> Result<()> {
+        let residues = [2u32, 3, 2].map(BigUint::from);
+        let moduli = [3u32, 5, 7].map(BigUint::from);
+        let (x, m) = crt(&residues.into_iter().zip(moduli).collect_vec())?;
+        assert_eq!(BigUint::from(23u32), x);
+        assert_eq!(BigUint::from(105u32), m);
+
+        Ok(())
+    }
+
+    #[test]
+    fn crt_reports_shared_factor() {
+        let residues = [1u32, 2, 3].map(BigUint::from);
+        let moduli = [35u32, 11, 21].map(BigUint::from);
+        let res = crt(&residues.into_iter().zip(moduli).collect_vec());
+        assert!(matches!(res, Err(Error::NotCoprime(f)) if f == BigUint::from(7u32)));
+    }
+
+    #[test]
+    fn crt_rejects_zero_modulus() {
+        let res = crt(&[(BigUint::one(), BigUint::zero())]);
+        assert!(matches!(res, Err(Error::DivisionByZero)));
+
+        let res = crt(&[
+            (BigUint::zero(), BigUint::one()),
+            (BigUint::one(), BigUint::zero()),
+        ]);
+        assert!(matches!(res, Err(Error::DivisionByZero)));
+    }
+}
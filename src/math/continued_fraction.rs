@@ -0,0 +1,91 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+/// The coefficients of the continued fraction expansion of `num / den`, produced lazily by the
+/// Euclidean algorithm.
+#[derive(Debug, Clone)]
+pub struct Expansion {
+    num: BigUint,
+    den: BigUint,
+}
+
+impl Expansion {
+    #[must_use]
+    pub fn new(num: BigUint, den: BigUint) -> Self {
+        Self { num, den }
+    }
+}
+
+impl Iterator for Expansion {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.den.is_zero() {
+            return None;
+        }
+
+        let (a, r) = self.num.div_rem(&self.den);
+        self.num = std::mem::replace(&mut self.den, r);
+        Some(a)
+    }
+}
+
+/// The convergents `h / k` of a continued fraction, as `(h, k)` pairs.
+#[derive(Debug, Clone)]
+pub struct Convergents<I> {
+    coefficients: I,
+    prev: (BigUint, BigUint),
+    curr: (BigUint, BigUint),
+}
+
+impl<I: Iterator<Item = BigUint>> Convergents<I> {
+    pub fn new(coefficients: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            coefficients: coefficients.into_iter(),
+            prev: (BigUint::zero(), BigUint::one()),
+            curr: (BigUint::one(), BigUint::zero()),
+        }
+    }
+}
+
+impl<I: Iterator<Item = BigUint>> Iterator for Convergents<I> {
+    type Item = (BigUint, BigUint);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let a = self.coefficients.next()?;
+        let h = &a * &self.curr.0 + &self.prev.0;
+        let k = &a * &self.curr.1 + &self.prev.1;
+
+        self.prev = std::mem::replace(&mut self.curr, (h.clone(), k.clone()));
+        Some((h, k))
+    }
+}
+
+#[must_use]
+pub fn convergents(num: BigUint, den: BigUint) -> Convergents<Expansion> {
+    Convergents::new(Expansion::new(num, den))
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn expands_rationals() {
+        let res = Expansion::new(415u32.into(), 93u32.into()).collect_vec();
+        assert_eq!([4u32, 2, 6, 7].map(BigUint::from).to_vec(), res);
+    }
+
+    #[test]
+    fn generates_convergents() {
+        let res = convergents(415u32.into(), 93u32.into()).collect_vec();
+        let expected = [(4u32, 1u32), (9, 2), (58, 13), (415, 93)]
+            .map(|(h, k)| (BigUint::from(h), BigUint::from(k)))
+            .to_vec();
+        assert_eq!(expected, res);
+    }
+}
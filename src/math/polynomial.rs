@@ -0,0 +1,169 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+use crate::{Error, Result, math::invmod};
+
+/// A polynomial over `Z_n`, with coefficients stored from the constant term upwards and no
+/// trailing zeroes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<BigUint>,
+    modulus: BigUint,
+}
+
+impl Polynomial {
+    #[must_use]
+    pub fn new(coefficients: Vec<BigUint>, modulus: BigUint) -> Self {
+        let coefficients = coefficients.into_iter().map(|c| c % &modulus).collect();
+        let mut res = Self {
+            coefficients,
+            modulus,
+        };
+        res.trim();
+        res
+    }
+
+    fn trim(&mut self) {
+        while self.coefficients.last().is_some_and(Zero::is_zero) {
+            self.coefficients.pop();
+        }
+    }
+
+    #[must_use]
+    pub fn coefficients(&self) -> &[BigUint] {
+        &self.coefficients
+    }
+
+    /// The degree of the polynomial, or `None` for the zero polynomial.
+    #[must_use]
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    #[must_use]
+    pub fn mul(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::new(vec![], self.modulus.clone());
+        }
+
+        let mut coefficients =
+            vec![BigUint::zero(); self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] += a * b;
+            }
+        }
+
+        Self::new(coefficients, self.modulus.clone())
+    }
+
+    #[must_use]
+    pub fn pow(&self, exp: u32) -> Self {
+        (0..exp).fold(
+            Self::new(vec![BigUint::one()], self.modulus.clone()),
+            |acc, _| acc.mul(self),
+        )
+    }
+
+    #[must_use]
+    pub fn sub(&self, other: &Self) -> Self {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        let zero = BigUint::zero();
+        let coefficients = (0..len)
+            .map(|i| {
+                let a = self.coefficients.get(i).unwrap_or(&zero);
+                let b = other.coefficients.get(i).unwrap_or(&zero);
+                a + &self.modulus - b
+            })
+            .collect();
+
+        Self::new(coefficients, self.modulus.clone())
+    }
+
+    /// The remainder of division by `divisor`. Fails on the zero polynomial, and with the shared
+    /// factor if the leading coefficient of `divisor` isn't invertible mod `n`.
+    pub fn rem(&self, divisor: &Self) -> Result<Self> {
+        let Some(degree) = divisor.degree() else {
+            return Err(Error::DivisionByZero);
+        };
+
+        let n = &self.modulus;
+        let lead = &divisor.coefficients[degree];
+        let lead_inv = invmod(lead, n).ok_or_else(|| Error::NotCoprime(lead.gcd(n)))?;
+
+        let mut rem = self.clone();
+        while let Some(d) = rem.degree()
+            && d >= degree
+        {
+            let factor = (&rem.coefficients[d] * &lead_inv) % n;
+            let shift = d - degree;
+            for (i, c) in divisor.coefficients.iter().enumerate() {
+                let sub = (&factor * c) % n;
+                rem.coefficients[i + shift] = (&rem.coefficients[i + shift] + n - sub) % n;
+            }
+            rem.trim();
+        }
+
+        Ok(rem)
+    }
+
+    /// Scales the polynomial so that its leading coefficient is one.
+    pub fn monic(&self) -> Result<Self> {
+        let Some(lead) = self.coefficients.last() else {
+            return Ok(self.clone());
+        };
+
+        let n = &self.modulus;
+        let lead_inv = invmod(lead, n).ok_or_else(|| Error::NotCoprime(lead.gcd(n)))?;
+        let coefficients = self.coefficients.iter().map(|c| c * &lead_inv).collect();
+        Ok(Self::new(coefficients, n.clone()))
+    }
+
+    /// The monic greatest common divisor, by the Euclidean algorithm.
+    pub fn gcd(&self, other: &Self) -> Result<Self> {
+        let mut a = self.clone();
+        let mut b = other.clone();
+
+        while !b.is_zero() {
+            let r = a.rem(&b)?;
+            a = std::mem::replace(&mut b, r);
+        }
+
+        a.monic()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn poly(coefficients: &[u32]) -> Polynomial {
+        let coefficients = coefficients.iter().copied().map(BigUint::from).collect();
+        Polynomial::new(coefficients, BigUint::from(101u32))
+    }
+
+    #[test]
+    fn gcd_finds_common_factor() -> Result<()> {
+        // (x + 3)(x + 5) and (x + 3)(x + 7)
+        let lhs = poly(&[3, 1]).mul(&poly(&[5, 1]));
+        let rhs = poly(&[3, 1]).mul(&poly(&[7, 1]));
+        assert_eq!(poly(&[3, 1]), lhs.gcd(&rhs)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rem_rejects_zero_divisor() {
+        let res = poly(&[3, 1]).rem(&poly(&[]));
+        assert!(matches!(res, Err(Error::DivisionByZero)));
+    }
+}
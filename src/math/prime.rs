@@ -0,0 +1,117 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
+use rand::Rng;
+
+use super::{random_below, random_bits};
+
+const MILLER_RABIN_ROUNDS: usize = 32;
+
+const SMALL_PRIMES: [u32; 54] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199, 211, 223, 227, 229, 233, 239, 241, 251,
+];
+
+pub fn is_probable_prime<R: Rng + ?Sized>(n: &BigUint, rng: &mut R) -> bool {
+    if let Some(n) = n.to_u32()
+        && n <= SMALL_PRIMES[SMALL_PRIMES.len() - 1]
+    {
+        return SMALL_PRIMES.contains(&n);
+    }
+
+    if SMALL_PRIMES.iter().any(|&p| (n % p).is_zero()) {
+        return false;
+    }
+
+    miller_rabin(n, MILLER_RABIN_ROUNDS, rng)
+}
+
+fn miller_rabin<R: Rng + ?Sized>(n: &BigUint, rounds: usize, rng: &mut R) -> bool {
+    let one = BigUint::one();
+    let two = BigUint::from(2u32);
+    let n_minus_one = n - &one;
+
+    let s = n_minus_one.trailing_zeros().unwrap_or(0);
+    let d = &n_minus_one >> s;
+
+    'witness: for _ in 0..rounds {
+        // a in [2, n - 2]
+        let a = random_below(&(n - 3u32), rng) + &two;
+        let mut x = a.modpow(&d, n);
+
+        if x == one || x == n_minus_one {
+            continue;
+        }
+
+        for _ in 1..s {
+            x = x.modpow(&two, n);
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+
+        return false;
+    }
+
+    true
+}
+
+pub fn gen_prime<R: Rng + ?Sized>(bits: u64, rng: &mut R) -> BigUint {
+    assert!(bits >= 2, "primes need at least two bits");
+
+    loop {
+        let mut n = random_bits(bits, rng);
+        // set the top two bits so that the product of two primes has exactly `2 * bits` bits
+        n.set_bit(bits - 1, true);
+        if bits > 2 {
+            n.set_bit(bits - 2, true);
+        }
+        if n.is_even() {
+            n += 1u32;
+        }
+
+        if is_probable_prime(&n, rng) {
+            return n;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn detects_primes() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let primes = [
+            2u64,
+            3,
+            251,
+            257,
+            65537,
+            2_147_483_647,
+            18_446_744_073_709_551_557,
+        ];
+        for p in primes {
+            assert!(is_probable_prime(&p.into(), &mut rng), "{p} is prime");
+        }
+
+        // 561 and 41041 are Carmichael numbers
+        let composites = [1u64, 4, 561, 41041, 65535, 4_294_967_297];
+        for c in composites {
+            assert!(!is_probable_prime(&c.into(), &mut rng), "{c} is composite");
+        }
+    }
+
+    #[test]
+    fn gen_prime_has_requested_size() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let p = gen_prime(128, &mut rng);
+        assert_eq!(128, p.bits());
+        assert!(is_probable_prime(&p, &mut rng));
+    }
+}
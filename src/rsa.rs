@@ -0,0 +1,279 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::One;
+use rand::Rng;
+
+use crate::{
+    Data, Error, Result,
+    math::{invmod, prime::gen_prime},
+};
+
+pub mod der;
+pub mod oaep;
+pub mod pkcs1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    pub e: BigUint,
+    pub n: BigUint,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivateKey {
+    pub e: BigUint,
+    pub n: BigUint,
+    pub d: BigUint,
+    pub p: BigUint,
+    pub q: BigUint,
+    dp: BigUint,
+    dq: BigUint,
+    qinv: BigUint,
+}
+
+impl PublicKey {
+    #[must_use]
+    pub fn new(e: BigUint, n: BigUint) -> Self {
+        Self { e, n }
+    }
+
+    #[must_use]
+    pub fn size(&self) -> usize {
+        usize::try_from(self.n.bits().div_ceil(8)).expect("modulus fits in memory")
+    }
+
+    pub fn encrypt_int(&self, m: &BigUint) -> Result<BigUint> {
+        if m >= &self.n {
+            return Err(Error::MessageOutOfRange);
+        }
+
+        Ok(m.modpow(&self.e, &self.n))
+    }
+
+    pub fn encrypt(&self, data: &Data) -> Result<Data> {
+        let c = self.encrypt_int(&data.biguint())?;
+        Ok(Data::from_biguint(&c))
+    }
+
+    #[must_use]
+    pub fn data(&self) -> Data {
+        encode(&[&self.e, &self.n])
+    }
+
+    pub fn from_data(data: &Data) -> Result<Self> {
+        let [e, n] = decode(data)?;
+        Ok(Self { e, n })
+    }
+}
+
+impl PrivateKey {
+    pub fn generate(bits: u64, e: &BigUint) -> Result<Self> {
+        Self::generate_with_rng(bits, e, &mut rand::rng())
+    }
+
+    pub fn generate_with_rng<R: Rng + ?Sized>(bits: u64, e: &BigUint, rng: &mut R) -> Result<Self> {
+        if bits < 16 {
+            return Err(Error::InvalidKey("modulus must be at least 16 bits"));
+        }
+        if e.is_even() || e < &BigUint::from(3u32) {
+            return Err(Error::InvalidKey(
+                "public exponent must be odd and at least 3",
+            ));
+        }
+
+        let gen_factor = |bits: u64, rng: &mut R| loop {
+            let p = gen_prime(bits, rng);
+            if (&p - 1u32).gcd(e).is_one() {
+                break p;
+            }
+        };
+
+        let p = gen_factor(bits - bits / 2, rng);
+        let q = loop {
+            let q = gen_factor(bits / 2, rng);
+            if q != p {
+                break q;
+            }
+        };
+
+        Self::from_primes(e.clone(), p, q)
+    }
+
+    pub fn from_primes(e: BigUint, p: BigUint, q: BigUint) -> Result<Self> {
+        let n = &p * &q;
+        let phi = (&p - 1u32) * (&q - 1u32);
+        let d = invmod(&e, &phi).ok_or(Error::InvalidKey("public exponent is not invertible"))?;
+
+        let dp = &d % (&p - 1u32);
+        let dq = &d % (&q - 1u32);
+        let qinv = invmod(&q, &p).ok_or(Error::InvalidKey("primes are not coprime"))?;
+
+        Ok(Self {
+            e,
+            n,
+            d,
+            p,
+            q,
+            dp,
+            dq,
+            qinv,
+        })
+    }
+
+    #[must_use]
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::new(self.e.clone(), self.n.clone())
+    }
+
+    pub fn decrypt_int(&self, c: &BigUint) -> Result<BigUint> {
+        if c >= &self.n {
+            return Err(Error::MessageOutOfRange);
+        }
+
+        Ok(c.modpow(&self.d, &self.n))
+    }
+
+    pub fn decrypt_int_crt(&self, c: &BigUint) -> Result<BigUint> {
+        if c >= &self.n {
+            return Err(Error::MessageOutOfRange);
+        }
+
+        let m1 = c.modpow(&self.dp, &self.p);
+        let m2 = c.modpow(&self.dq, &self.q);
+        // h = qinv * (m1 - m2) mod p, kept non-negative
+        let h = (&self.qinv * (&m1 + &self.p - (&m2 % &self.p))) % &self.p;
+
+        Ok(m2 + h * &self.q)
+    }
+
+    pub fn decrypt(&self, data: &Data) -> Result<Data> {
+        let m = self.decrypt_int(&data.biguint())?;
+        Ok(Data::from_biguint(&m))
+    }
+
+    pub fn decrypt_crt(&self, data: &Data) -> Result<Data> {
+        let m = self.decrypt_int_crt(&data.biguint())?;
+        Ok(Data::from_biguint(&m))
+    }
+
+    #[must_use]
+    pub fn data(&self) -> Data {
+        encode(&[&self.e, &self.p, &self.q])
+    }
+
+    pub fn from_data(data: &Data) -> Result<Self> {
+        let [e, p, q] = decode(data)?;
+        Self::from_primes(e, p, q)
+    }
+}
+
+/// Serializes each integer as a big-endian `u32` length followed by its big-endian bytes.
+fn encode(ints: &[&BigUint]) -> Data {
+    let bytes: Box<[u8]> = ints
+        .iter()
+        .flat_map(|n| {
+            let bytes = n.to_bytes_be();
+            let len = u32::try_from(bytes.len()).expect("integer length fits in u32");
+            len.to_be_bytes().into_iter().chain(bytes)
+        })
+        .collect();
+    Data::from(bytes)
+}
+
+fn decode<const N: usize>(data: &Data) -> Result<[BigUint; N]> {
+    let mut rest: &[u8] = data;
+    let mut ints = Vec::with_capacity(N);
+
+    for _ in 0..N {
+        let Some((len, tail)) = rest.split_first_chunk::<4>() else {
+            return Err(Error::InvalidKey("truncated length prefix"));
+        };
+        let len = u32::from_be_bytes(*len) as usize;
+        let Some((bytes, tail)) = tail.split_at_checked(len) else {
+            return Err(Error::InvalidKey("truncated integer"));
+        };
+
+        ints.push(BigUint::from_bytes_be(bytes));
+        rest = tail;
+    }
+
+    if !rest.is_empty() {
+        return Err(Error::InvalidKey("trailing bytes after key"));
+    }
+
+    Ok(ints
+        .try_into()
+        .expect("the loop decodes exactly N integers"))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn s5c39_implement_rsa() -> Result<()> {
+        let e = BigUint::from(3u32);
+        let key = PrivateKey::generate(512, &e)?;
+        let public = key.public_key();
+        assert_eq!(512, key.n.bits());
+
+        let data = Data::from("hello, world!".as_bytes());
+        let encrypted = public.encrypt(&data)?;
+        assert_eq!("hello, world!", key.decrypt(&encrypted)?);
+        assert_eq!("hello, world!", key.decrypt_crt(&encrypted)?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "slow"]
+    fn s5c39_implement_rsa_2048() -> Result<()> {
+        let e = BigUint::from(65537u32);
+        let key = PrivateKey::generate(2048, &e)?;
+        assert_eq!(2048, key.n.bits());
+
+        let data = Data::from("hello, world!".as_bytes());
+        let encrypted = key.public_key().encrypt(&data)?;
+        assert_eq!("hello, world!", key.decrypt_crt(&encrypted)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn keygen_is_reproducible() -> Result<()> {
+        let e = BigUint::from(65537u32);
+        let lhs = PrivateKey::generate_with_rng(256, &e, &mut StdRng::seed_from_u64(39))?;
+        let rhs = PrivateKey::generate_with_rng(256, &e, &mut StdRng::seed_from_u64(39))?;
+        assert_eq!(lhs, rhs);
+
+        Ok(())
+    }
+
+    #[test]
+    fn keys_roundtrip_through_hex() -> Result<()> {
+        let e = BigUint::from(3u32);
+        let key = PrivateKey::generate_with_rng(256, &e, &mut StdRng::seed_from_u64(39))?;
+
+        let hex = key.data().hex();
+        assert_eq!(key, PrivateKey::from_data(&Data::from_hex(hex)?)?);
+
+        let public = key.public_key();
+        let hex = public.data().hex();
+        assert_eq!(public, PublicKey::from_data(&Data::from_hex(hex)?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_out_of_range_messages() -> Result<()> {
+        let e = BigUint::from(3u32);
+        let key = PrivateKey::generate_with_rng(64, &e, &mut StdRng::seed_from_u64(39))?;
+        let res = key.public_key().encrypt_int(&key.n);
+        assert!(matches!(res, Err(Error::MessageOutOfRange)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+use super::{PrivateKey, PublicKey};
+use crate::{
+    Data, Error, Result,
+    der::{Der, RSA_ENCRYPTION},
+};
+
+fn algorithm() -> Der {
+    Der::Sequence(vec![
+        Der::ObjectIdentifier(RSA_ENCRYPTION.to_vec()),
+        Der::Null,
+    ])
+}
+
+fn check_algorithm(der: &Der) -> Result<()> {
+    match der.as_sequence()? {
+        [oid, Der::Null] | [oid] if oid.as_oid()? == RSA_ENCRYPTION => Ok(()),
+        _ => Err(Error::InvalidKey("not an RSA key")),
+    }
+}
+
+impl PublicKey {
+    /// `RSAPublicKey ::= SEQUENCE { n, e }`
+    #[must_use]
+    pub fn pkcs1_der(&self) -> Data {
+        Der::Sequence(vec![(&self.n).into(), (&self.e).into()]).encode()
+    }
+
+    pub fn from_pkcs1_der(data: &Data) -> Result<Self> {
+        let der = Der::decode(data)?;
+        let [n, e] = der.as_sequence()? else {
+            return Err(Error::InvalidEncoding);
+        };
+
+        Ok(Self::new(e.as_integer()?.clone(), n.as_integer()?.clone()))
+    }
+
+    #[must_use]
+    pub fn spki_der(&self) -> Data {
+        Der::Sequence(vec![
+            algorithm(),
+            Der::BitString {
+                unused: 0,
+                data: self.pkcs1_der(),
+            },
+        ])
+        .encode()
+    }
+
+    pub fn from_spki_der(data: &Data) -> Result<Self> {
+        let der = Der::decode(data)?;
+        let [algorithm, key] = der.as_sequence()? else {
+            return Err(Error::InvalidEncoding);
+        };
+
+        check_algorithm(algorithm)?;
+        Self::from_pkcs1_der(key.as_bit_string()?)
+    }
+}
+
+impl PrivateKey {
+    /// `RSAPrivateKey ::= SEQUENCE { version, n, e, d, p, q, dp, dq, qinv }`
+    #[must_use]
+    pub fn pkcs1_der(&self) -> Data {
+        Der::Sequence(vec![
+            BigUint::zero().into(),
+            (&self.n).into(),
+            (&self.e).into(),
+            (&self.d).into(),
+            (&self.p).into(),
+            (&self.q).into(),
+            (&self.dp).into(),
+            (&self.dq).into(),
+            (&self.qinv).into(),
+        ])
+        .encode()
+    }
+
+    /// Rebuilds the key from `e`, `p` and `q`, keeping the file's `d` once it's checked to be a
+    /// valid exponent (tools usually reduce it mod `lcm(p - 1, q - 1)` rather than `phi`).
+    pub fn from_pkcs1_der(data: &Data) -> Result<Self> {
+        let der = Der::decode(data)?;
+        let [version, rest @ ..] = der.as_sequence()? else {
+            return Err(Error::InvalidEncoding);
+        };
+        if !version.as_integer()?.is_zero() {
+            return Err(Error::InvalidKey("multi-prime keys are unsupported"));
+        }
+
+        let ints = rest
+            .iter()
+            .map(Der::as_integer)
+            .collect::<Result<Vec<_>>>()?;
+        let [n, e, d, p, q, dp, dq, qinv] = ints[..] else {
+            return Err(Error::InvalidEncoding);
+        };
+
+        let mut key = Self::from_primes(e.clone(), p.clone(), q.clone())?;
+        let lambda = (p - 1u32).lcm(&(q - 1u32));
+        if &key.n != n || !((e * d) % lambda).is_one() {
+            return Err(Error::InvalidKey("inconsistent private key"));
+        }
+        if (&key.dp, &key.dq, &key.qinv) != (dp, dq, qinv) {
+            return Err(Error::InvalidKey("inconsistent CRT parameters"));
+        }
+
+        key.d = d.clone();
+        Ok(key)
+    }
+
+    /// `PrivateKeyInfo ::= SEQUENCE { version, algorithm, OCTET STRING { RSAPrivateKey } }`
+    #[must_use]
+    pub fn pkcs8_der(&self) -> Data {
+        Der::Sequence(vec![
+            BigUint::zero().into(),
+            algorithm(),
+            Der::OctetString(self.pkcs1_der()),
+        ])
+        .encode()
+    }
+
+    pub fn from_pkcs8_der(data: &Data) -> Result<Self> {
+        let der = Der::decode(data)?;
+        let [version, algorithm, key] = der.as_sequence()? else {
+            return Err(Error::InvalidEncoding);
+        };
+        if !version.as_integer()?.is_zero() {
+            return Err(Error::InvalidEncoding);
+        }
+
+        check_algorithm(algorithm)?;
+        Self::from_pkcs1_der(key.as_octet_string()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::rsa::pkcs1::{self, HashAlgorithm};
+
+    #[test]
+    fn reads_and_writes_openssl_keys() -> Result<()> {
+        let pkcs1 = include_str!("../../data/rsa_pkcs1.pem");
+        let pkcs8 = include_str!("../../data/rsa_pkcs8.pem");
+        let spki = include_str!("../../data/rsa_spki.pem");
+        let pkcs1_pub = include_str!("../../data/rsa_pkcs1_pub.pem");
+
+        let key = PrivateKey::from_pkcs1_der(&Data::from_pem(pkcs1, "RSA PRIVATE KEY")?)?;
+        assert_eq!(pkcs1, key.pkcs1_der().pem("RSA PRIVATE KEY"));
+
+        let other = PrivateKey::from_pkcs8_der(&Data::from_pem(pkcs8, "PRIVATE KEY")?)?;
+        assert_eq!(key, other);
+        assert_eq!(pkcs8, key.pkcs8_der().pem("PRIVATE KEY"));
+
+        let public = PublicKey::from_spki_der(&Data::from_pem(spki, "PUBLIC KEY")?)?;
+        assert_eq!(key.public_key(), public);
+        assert_eq!(spki, public.spki_der().pem("PUBLIC KEY"));
+
+        let public = PublicKey::from_pkcs1_der(&Data::from_pem(pkcs1_pub, "RSA PUBLIC KEY")?)?;
+        assert_eq!(pkcs1_pub, public.pkcs1_der().pem("RSA PUBLIC KEY"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn interoperates_with_openssl() -> Result<()> {
+        let pem = include_str!("../../data/rsa_pkcs1.pem");
+        let key = PrivateKey::from_pkcs1_der(&Data::from_pem(pem, "RSA PRIVATE KEY")?)?;
+        let msg = Data::from(b"hi mom".as_slice());
+
+        let ct = Data::from_base64(include_str!("../../data/rsa_pkcs1_ct.b64").trim())?;
+        assert_eq!(pkcs1::decrypt(&key, &ct)?, msg);
+
+        let sig = Data::from_base64(include_str!("../../data/rsa_sha256_sig.b64").trim())?;
+        assert!(pkcs1::verify(
+            &key.public_key(),
+            HashAlgorithm::Sha256,
+            &msg,
+            &sig
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_other_algorithms() -> Result<()> {
+        let spki = include_str!("../../data/dsa_spki.pem");
+        let data = Data::from_pem(spki, "PUBLIC KEY")?;
+        assert!(PublicKey::from_spki_der(&data).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use sha2::Digest;
+
+use crate::{
+    Data, Error, Result,
+    error::InvalidLengthType,
+    rsa::{PrivateKey, PublicKey},
+};
+
+/// MGF1 mask generation: the concatenated digests of `seed || counter`, truncated to `len` bytes.
+#[must_use]
+pub fn mgf1<D: Digest>(seed: &[u8], len: usize) -> Data {
+    let bytes: Box<[u8]> = (0u32..)
+        .flat_map(|counter| {
+            let mut hasher = D::new();
+            hasher.update(seed);
+            hasher.update(counter.to_be_bytes());
+            hasher.finalize()
+        })
+        .take(len)
+        .collect();
+    Data::from(bytes)
+}
+
+/// Builds `00 || maskedSeed || maskedDB` for a `len`-byte modulus, where
+/// `DB = lHash || 00 .. 00 || 01 || M`.
+pub fn encode<D: Digest>(message: &Data, label: &Data, len: usize) -> Result<Data> {
+    let hlen = <D as Digest>::output_size();
+    let Some(padding) = len
+        .checked_sub(message.len() + 2 * hlen + 2)
+        .filter(|_| len > 2 * hlen + 1)
+    else {
+        return Err(Error::InvalidLength {
+            kind: InvalidLengthType::Message,
+            expected: len.saturating_sub(2 * hlen + 2),
+            actual: message.len(),
+        });
+    };
+
+    let db: Box<[u8]> = D::digest(label)
+        .into_iter()
+        .chain(std::iter::repeat_n(0x00, padding))
+        .chain([0x01])
+        .chain(message.iter().copied())
+        .collect();
+    let db = Data::from(db);
+
+    let seed: Box<[u8]> = (0..hlen).map(|_| rand::random()).collect();
+    let masked_db = db ^ mgf1::<D>(&seed, len - hlen - 1);
+    let masked_seed = Data::from(seed) ^ mgf1::<D>(&masked_db, hlen);
+
+    let bytes: Box<[u8]> = [0x00]
+        .into_iter()
+        .chain(masked_seed.iter().copied())
+        .chain(masked_db.iter().copied())
+        .collect();
+    Ok(Data::from(bytes))
+}
+
+pub fn decode<D: Digest>(em: &Data, label: &Data) -> Result<Data> {
+    let hlen = <D as Digest>::output_size();
+    if em.len() < 2 * hlen + 2 {
+        return Err(Error::InvalidEncoding);
+    }
+
+    let (y, rest) = em.split_at(1);
+    let (masked_seed, masked_db) = rest.split_at(hlen);
+    let seed = Data::from(masked_seed) ^ mgf1::<D>(masked_db, hlen);
+    let db = Data::from(masked_db) ^ mgf1::<D>(&seed, masked_db.len());
+
+    let (lhash, rest) = db.split_at(hlen);
+    let separator = rest.iter().position(|&b| b != 0x00);
+
+    // check everything before reporting, so that failures are indistinguishable
+    let valid = y[0] == 0x00
+        && D::digest(label).as_slice() == lhash
+        && separator.is_some_and(|i| rest[i] == 0x01);
+
+    match separator {
+        Some(i) if valid => Ok(Data::from(&rest[i + 1..])),
+        _ => Err(Error::InvalidEncoding),
+    }
+}
+
+pub fn encrypt<D: Digest>(key: &PublicKey, message: &Data, label: &Data) -> Result<Data> {
+    let len = key.size();
+    let em = encode::<D>(message, label, len)?;
+    let c = key.encrypt_int(&em.biguint())?;
+    Ok(Data::from_biguint_sized(&c, len))
+}
+
+pub fn decrypt<D: Digest>(key: &PrivateKey, data: &Data, label: &Data) -> Result<Data> {
+    let len = key.public_key().size();
+    let m = key.decrypt_int_crt(&data.biguint())?;
+    decode::<D>(&Data::from_biguint_sized(&m, len), label)
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use num_bigint::BigUint;
+    use pretty_assertions::assert_eq;
+    use sha1::Sha1;
+    use sha2::Sha256;
+
+    use super::*;
+
+    #[test]
+    fn encrypt_and_decrypt() -> Result<()> {
+        let key = PrivateKey::generate(1024, &BigUint::from(65537u32))?;
+        let public = key.public_key();
+        let message = Data::from("kick it, CC".as_bytes());
+        let label = Data::from("label".as_bytes());
+
+        let c = encrypt::<Sha256>(&public, &message, &label)?;
+        assert_eq!(message, decrypt::<Sha256>(&key, &c, &label)?);
+        assert!(decrypt::<Sha256>(&key, &c, &Data::from(vec![])).is_err());
+
+        let c = encrypt::<Sha1>(&public, &message, &label)?;
+        assert_eq!(message, decrypt::<Sha1>(&key, &c, &label)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+use rand::Rng;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    Data, Error, Result,
+    error::InvalidLengthType,
+    rsa::{PrivateKey, PublicKey},
+};
+
+const SHA1_DIGEST_INFO: &[u8] = &[
+    0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14,
+];
+
+const SHA256_DIGEST_INFO: &[u8] = &[
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
+    0x00, 0x04, 0x20,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+}
+
+impl HashAlgorithm {
+    const ALL: [Self; 2] = [Self::Sha1, Self::Sha256];
+
+    /// The DER-encoded `DigestInfo` header that precedes the digest.
+    #[must_use]
+    pub fn digest_info_prefix(self) -> &'static [u8] {
+        match self {
+            HashAlgorithm::Sha1 => SHA1_DIGEST_INFO,
+            HashAlgorithm::Sha256 => SHA256_DIGEST_INFO,
+        }
+    }
+
+    #[must_use]
+    pub fn digest_len(self) -> usize {
+        match self {
+            HashAlgorithm::Sha1 => 20,
+            HashAlgorithm::Sha256 => 32,
+        }
+    }
+
+    #[must_use]
+    pub fn digest(self, data: &Data) -> Data {
+        match self {
+            HashAlgorithm::Sha1 => Data::from(Sha1::digest(data).as_slice()),
+            HashAlgorithm::Sha256 => Data::from(Sha256::digest(data).as_slice()),
+        }
+    }
+
+    #[must_use]
+    pub fn digest_info(self, data: &Data) -> Data {
+        let bytes: Box<[u8]> = self
+            .digest_info_prefix()
+            .iter()
+            .chain(self.digest(data).iter())
+            .copied()
+            .collect();
+        Data::from(bytes)
+    }
+}
+
+/// Builds the `00 01 FF .. FF 00 || DigestInfo` block for a `len`-byte modulus.
+pub fn encode_signature(hash: HashAlgorithm, message: &Data, len: usize) -> Result<Data> {
+    let digest_info = hash.digest_info(message);
+    let Some(padding) = len.checked_sub(digest_info.len() + 3).filter(|&n| n >= 8) else {
+        return Err(Error::InvalidKey("modulus is too short for the digest"));
+    };
+
+    let bytes: Box<[u8]> = [0x00, 0x01]
+        .into_iter()
+        .chain(std::iter::repeat_n(0xff, padding))
+        .chain([0x00])
+        .chain(digest_info.iter().copied())
+        .collect();
+    Ok(Data::from(bytes))
+}
+
+pub fn sign(key: &PrivateKey, hash: HashAlgorithm, message: &Data) -> Result<Data> {
+    let len = key.public_key().size();
+    let em = encode_signature(hash, message, len)?;
+    let s = key.decrypt_int_crt(&em.biguint())?;
+    Ok(Data::from_biguint_sized(&s, len))
+}
+
+fn recover(key: &PublicKey, signature: &Data) -> Option<Data> {
+    let len = key.size();
+    if signature.len() != len {
+        return None;
+    }
+
+    let m = key.encrypt_int(&signature.biguint()).ok()?;
+    Some(Data::from_biguint_sized(&m, len))
+}
+
+#[must_use]
+pub fn verify(key: &PublicKey, hash: HashAlgorithm, message: &Data, signature: &Data) -> bool {
+    let Some(em) = recover(key, signature) else {
+        return false;
+    };
+
+    encode_signature(hash, message, key.size()).is_ok_and(|expected| em == expected)
+}
+
+/// A broken verifier that skips over the padding and never checks that the digest ends the block,
+/// so anything may follow it.
+#[must_use]
+pub fn verify_sloppy(key: &PublicKey, message: &Data, signature: &Data) -> bool {
+    let Some(em) = recover(key, signature) else {
+        return false;
+    };
+
+    let Some(rest) = em.strip_prefix(&[0x00, 0x01]) else {
+        return false;
+    };
+    let rest = match rest.iter().position(|&b| b != 0xff) {
+        Some(n) if n > 0 => &rest[n..],
+        _ => return false,
+    };
+    let Some(rest) = rest.strip_prefix(&[0x00]) else {
+        return false;
+    };
+
+    HashAlgorithm::ALL.into_iter().any(|hash| {
+        rest.strip_prefix(hash.digest_info_prefix())
+            .and_then(|rest| rest.get(..hash.digest_len()))
+            .is_some_and(|digest| hash.digest(message) == digest)
+    })
+}
+
+/// Builds the `00 02 || PS || 00 || M` block for a `len`-byte modulus, where `PS` is at least eight
+/// random nonzero bytes.
+pub fn encode_encryption(message: &Data, len: usize) -> Result<Data> {
+    let Some(padding) = len.checked_sub(message.len() + 3).filter(|&n| n >= 8) else {
+        return Err(Error::InvalidLength {
+            kind: InvalidLengthType::Message,
+            expected: len.saturating_sub(11),
+            actual: message.len(),
+        });
+    };
+
+    let mut rng = rand::rng();
+    let bytes: Box<[u8]> = [0x00, 0x02]
+        .into_iter()
+        .chain((0..padding).map(|_| rng.random_range(1..=0xff)))
+        .chain([0x00])
+        .chain(message.iter().copied())
+        .collect();
+    Ok(Data::from(bytes))
+}
+
+pub fn decode_encryption(em: &Data) -> Result<Data> {
+    let Some(rest) = em.strip_prefix(&[0x00, 0x02]) else {
+        return Err(Error::InvalidEncoding);
+    };
+    let Some(separator) = rest.iter().position(|&b| b == 0x00).filter(|&n| n >= 8) else {
+        return Err(Error::InvalidEncoding);
+    };
+
+    Ok(Data::from(&rest[separator + 1..]))
+}
+
+pub fn encrypt(key: &PublicKey, message: &Data) -> Result<Data> {
+    let len = key.size();
+    let em = encode_encryption(message, len)?;
+    let c = key.encrypt_int(&em.biguint())?;
+    Ok(Data::from_biguint_sized(&c, len))
+}
+
+pub fn decrypt(key: &PrivateKey, data: &Data) -> Result<Data> {
+    let len = key.public_key().size();
+    let m = key.decrypt_int_crt(&data.biguint())?;
+    decode_encryption(&Data::from_biguint_sized(&m, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use num_bigint::BigUint;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn sign_and_verify() -> Result<()> {
+        let key = PrivateKey::generate(1024, &BigUint::from(65537u32))?;
+        let public = key.public_key();
+        let message = Data::from("hi mom".as_bytes());
+        let other = Data::from("hi dad".as_bytes());
+
+        for hash in HashAlgorithm::ALL {
+            let signature = sign(&key, hash, &message)?;
+            assert!(verify(&public, hash, &message, &signature));
+            assert!(verify_sloppy(&public, &message, &signature));
+            assert!(!verify(&public, hash, &other, &signature));
+            assert!(!verify_sloppy(&public, &other, &signature));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn encrypt_and_decrypt() -> Result<()> {
+        let key = PrivateKey::generate(512, &BigUint::from(65537u32))?;
+        let message = Data::from("kick it, CC".as_bytes());
+
+        let c = encrypt(&key.public_key(), &message)?;
+        assert_eq!(message, decrypt(&key, &c)?);
+
+        let res = encrypt(&key.public_key(), &Data::from(vec![0; 54]));
+        assert!(res.is_err());
+
+        Ok(())
+    }
+}